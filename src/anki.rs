@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::errors::{LoadError, Position};
+use crate::history::{Review, ReviewLog};
+use crate::{Card, Cards, FlashCardState, FlashCards, Grade};
+
+// Anki separates the fields of a note with the ASCII unit separator.
+const FIELD_SEPARATOR: char = '\x1f';
+const COLLECTION_NAMES: [&str; 2] = ["collection.anki21", "collection.anki2"];
+// Written by Anki 2.1.50 and later, compressed with zstd and next to a
+// collection.anki2 that only holds a card asking to update Anki.
+const COMPRESSED_COLLECTION_NAME: &str = "collection.anki21b";
+
+#[derive(Debug, Default)]
+pub struct AnkiDeck {
+    pub cards: Cards<Card>,
+    pub tags: HashMap<String, Vec<String>>,
+    pub review_log: ReviewLog,
+}
+
+pub struct Anki {}
+
+impl Anki {
+    pub fn import(path: impl AsRef<Path>) -> Result<AnkiDeck, LoadError> {
+        let collection = Anki::extract_collection(File::open(path)?)?;
+        let result = Anki::import_collection(&collection);
+        let _ = std::fs::remove_file(&collection);
+
+        result
+    }
+
+    // SQLite needs a real file, so the collection is copied out of the
+    // archive into the temp directory before it is opened.
+    fn extract_collection(archive: File) -> Result<PathBuf, LoadError> {
+        let mut archive = zip::ZipArchive::new(archive).map_err(zip_error)?;
+        if archive.index_for_name(COMPRESSED_COLLECTION_NAME).is_some() {
+            return Err(malformed(
+                "apkg archive uses the compressed collection.anki21b format, export it \
+                 with \"Support older Anki versions\" checked"
+                    .to_string(),
+            ));
+        }
+
+        let name = COLLECTION_NAMES
+            .iter()
+            .find(|name| archive.index_for_name(name).is_some())
+            .ok_or_else(|| {
+                malformed("apkg archive does not contain an Anki collection".to_string())
+            })?;
+        let mut entry = archive.by_name(name).map_err(zip_error)?;
+
+        let (path, mut file) = Anki::create_temp_file()?;
+        if let Err(err) = std::io::copy(&mut entry, &mut file) {
+            drop(file);
+            let _ = std::fs::remove_file(&path);
+            return Err(err.into());
+        }
+
+        Ok(path)
+    }
+
+    // create_new fails rather than opening a file someone else put at the
+    // same path, in which case another name is tried.
+    fn create_temp_file() -> Result<(PathBuf, File), LoadError> {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        for attempt in 0..16 {
+            let path = std::env::temp_dir().join(format!(
+                "flash_cards_anki_{}_{}_{}.sqlite",
+                std::process::id(),
+                nanos,
+                attempt
+            ));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Err(Error::new(
+            ErrorKind::AlreadyExists,
+            "could not create a temporary collection",
+        )
+        .into())
+    }
+
+    pub fn import_collection(path: impl AsRef<Path>) -> Result<AnkiDeck, LoadError> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(sqlite_error)?;
+        let mut deck = AnkiDeck::default();
+        let mut note_ids = HashMap::new();
+
+        let mut stmt = conn
+            .prepare("SELECT id, guid, tags, flds FROM notes ORDER BY id")
+            .map_err(sqlite_error)?;
+        let mut rows = stmt.query([]).map_err(sqlite_error)?;
+
+        while let Some(row) = rows.next().map_err(sqlite_error)? {
+            let note_id: i64 = row.get(0).map_err(sqlite_error)?;
+            let guid: String = row.get(1).map_err(sqlite_error)?;
+            let tags: String = row.get(2).map_err(sqlite_error)?;
+            let fields: String = row.get(3).map_err(sqlite_error)?;
+
+            let mut fields = fields.split(FIELD_SEPARATOR);
+            let (front, back) = match (fields.next(), fields.next()) {
+                (Some(front), Some(back)) => (html_to_text(front), html_to_text(back)),
+                _ => continue,
+            };
+            let hint = fields
+                .next()
+                .map(html_to_text)
+                .filter(|hint| !hint.is_empty());
+
+            let card_id = format!("anki-{}", guid);
+            let mut card = Card::new(front, back, String::new());
+            card.set_hint(hint);
+            card.set_id(card_id.clone());
+            deck.cards.add_card(card);
+
+            let tags: Vec<String> = tags.split_whitespace().map(|t| t.to_string()).collect();
+            if !tags.is_empty() {
+                deck.tags.insert(card_id.clone(), tags);
+            }
+            note_ids.insert(note_id, card_id);
+        }
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT revlog.id, cards.nid, revlog.ease, revlog.time FROM revlog \
+                 JOIN cards ON cards.id = revlog.cid ORDER BY revlog.id",
+            )
+            .map_err(sqlite_error)?;
+        let mut rows = stmt.query([]).map_err(sqlite_error)?;
+
+        while let Some(row) = rows.next().map_err(sqlite_error)? {
+            let reviewed_at: i64 = row.get(0).map_err(sqlite_error)?;
+            let note_id: i64 = row.get(1).map_err(sqlite_error)?;
+            let ease: i64 = row.get(2).map_err(sqlite_error)?;
+            let time_taken: i64 = row.get(3).map_err(sqlite_error)?;
+
+            // An ease of 0 marks a manual reschedule rather than an answer.
+            let grade = match ease {
+                1 => Grade::Again,
+                2 => Grade::Hard,
+                3 => Grade::Good,
+                4 => Grade::Easy,
+                _ => continue,
+            };
+            let card_id = match note_ids.get(&note_id) {
+                Some(card_id) => card_id.clone(),
+                None => continue,
+            };
+
+            deck.review_log.record(Review::new(
+                card_id,
+                SystemTime::UNIX_EPOCH + Duration::from_millis(reviewed_at.max(0) as u64),
+                grade,
+                Duration::from_millis(time_taken.max(0) as u64),
+                FlashCardState::Back,
+            ));
+        }
+
+        Ok(deck)
+    }
+}
+
+// Anki keeps fields as HTML. Line breaks and blocks become new lines, other
+// tags are dropped and the common entities are decoded. Media references
+// such as <img> are lost.
+fn html_to_text(field: &str) -> String {
+    let mut text = String::new();
+    let mut rest = field;
+
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with('<') {
+            let Some(end) = rest.find('>') else { break };
+            let tag = rest[1..end].trim().to_ascii_lowercase();
+            let name = tag.split([' ', '/']).find(|name| !name.is_empty());
+            let closing = tag.starts_with('/');
+            match name {
+                Some("br") => text.push('\n'),
+                Some("div" | "p" | "li") if closing => text.push('\n'),
+                _ => {}
+            }
+            rest = &rest[end + 1..];
+        } else {
+            let entity = rest.find(';').map(|end| (&rest[..=end], end));
+            let decoded = entity.and_then(|(entity, end)| {
+                let c = match entity {
+                    "&nbsp;" => ' ',
+                    "&amp;" => '&',
+                    "&lt;" => '<',
+                    "&gt;" => '>',
+                    "&quot;" => '"',
+                    "&#39;" | "&apos;" => '\'',
+                    _ => return None,
+                };
+                Some((c, end))
+            });
+            match decoded {
+                Some((c, end)) => {
+                    text.push(c);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+    }
+    text.push_str(rest);
+
+    text.trim().to_string()
+}
+
+fn malformed(message: String) -> LoadError {
+    LoadError::Malformed {
+        position: Position::default(),
+        message,
+    }
+}
+
+fn zip_error(err: zip::result::ZipError) -> LoadError {
+    match err {
+        zip::result::ZipError::Io(err) => LoadError::Io(err),
+        err => malformed(err.to_string()),
+    }
+}
+
+fn sqlite_error(err: rusqlite::Error) -> LoadError {
+    malformed(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FlashCard;
+    use std::io::Write;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("flash_cards_{}_{}", std::process::id(), name))
+    }
+
+    fn create_test_collection(path: &Path) {
+        let _ = std::fs::remove_file(path);
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "
+            CREATE TABLE notes (id INTEGER PRIMARY KEY, guid TEXT, tags TEXT, flds TEXT);
+            CREATE TABLE cards (id INTEGER PRIMARY KEY, nid INTEGER);
+            CREATE TABLE revlog (id INTEGER PRIMARY KEY, cid INTEGER, ease INTEGER, time INTEGER);
+            INSERT INTO notes VALUES (1, 'abc', ' geo europe ', 'France\x1fParis\x1fEiffel');
+            INSERT INTO notes VALUES (2, 'def', '', 'Germany\x1fBerlin');
+            INSERT INTO notes VALUES (3, 'ghi', '', 'Lonely field');
+            INSERT INTO cards VALUES (10, 1);
+            INSERT INTO cards VALUES (20, 2);
+            INSERT INTO revlog VALUES (1000, 10, 3, 4000);
+            INSERT INTO revlog VALUES (2000, 10, 1, 9000);
+            INSERT INTO revlog VALUES (3000, 20, 0, 0);
+            ",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_import_apkg() {
+        let collection = temp_path("collection.anki2");
+        create_test_collection(&collection);
+
+        let apkg = temp_path("deck.apkg");
+        let mut zip = zip::ZipWriter::new(File::create(&apkg).unwrap());
+        zip.start_file("collection.anki2", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&std::fs::read(&collection).unwrap()).unwrap();
+        zip.finish().unwrap();
+
+        let mut deck = Anki::import(&apkg).unwrap();
+        std::fs::remove_file(&collection).unwrap();
+        std::fs::remove_file(&apkg).unwrap();
+
+        assert_eq!(2, deck.cards.deck_size());
+        let card_1 = deck.cards.draw().unwrap();
+        let card_2 = deck.cards.draw().unwrap();
+
+        assert_eq!(card_1.get_front(), "France".to_string());
+        assert_eq!(card_1.get_back(), "Paris".to_string());
+        assert_eq!(card_1.get_hint(), Some("Eiffel".to_string()));
+        assert_eq!(card_1.get_id(), "anki-abc".to_string());
+        assert_eq!(card_2.get_hint(), None);
+
+        assert_eq!(
+            deck.tags.get("anki-abc"),
+            Some(&vec!["geo".to_string(), "europe".to_string()])
+        );
+        assert!(!deck.tags.contains_key("anki-def"));
+
+        let reviews = deck.review_log.reviews();
+        assert_eq!(2, reviews.len());
+        assert_eq!(reviews[0].grade, Grade::Good);
+        assert_eq!(reviews[1].grade, Grade::Again);
+        assert_eq!(reviews[1].time_taken(), Duration::from_secs(9));
+        assert_eq!(reviews[1].reviewed_at, 2);
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(html_to_text("Paris"), "Paris".to_string());
+        assert_eq!(
+            html_to_text("<div>Paris&nbsp;&amp; Lyon</div><div>Nice<br/>Lille</div>"),
+            "Paris & Lyon\nNice\nLille".to_string()
+        );
+        assert_eq!(
+            html_to_text("<b>a &lt; b</b> &unknown; <i>c"),
+            "a < b &unknown; c".to_string()
+        );
+        assert_eq!(html_to_text("<br>"), String::new());
+    }
+
+    #[test]
+    fn test_import_compressed_collection() {
+        let apkg = temp_path("compressed.apkg");
+        let mut zip = zip::ZipWriter::new(File::create(&apkg).unwrap());
+        for name in ["collection.anki2", "collection.anki21b"] {
+            zip.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"data").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let result = Anki::import(&apkg);
+        std::fs::remove_file(&apkg).unwrap();
+
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("collection.anki21b"));
+    }
+
+    #[test]
+    fn test_create_temp_file() {
+        let (path_1, _) = Anki::create_temp_file().unwrap();
+        let (path_2, _) = Anki::create_temp_file().unwrap();
+        let exists = path_1.exists() && path_2.exists();
+        std::fs::remove_file(&path_1).unwrap();
+        std::fs::remove_file(&path_2).unwrap();
+
+        assert_ne!(path_1, path_2);
+        assert!(exists);
+    }
+
+    #[test]
+    fn test_import_apkg_without_collection() {
+        let apkg = temp_path("empty.apkg");
+        let mut zip = zip::ZipWriter::new(File::create(&apkg).unwrap());
+        zip.start_file("media", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.finish().unwrap();
+
+        let result = Anki::import(&apkg);
+        std::fs::remove_file(&apkg).unwrap();
+
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "apkg archive does not contain an Anki collection".to_string()
+        );
+    }
+}
@@ -1,16 +1,18 @@
 use std::fmt::Display;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{FlashCard, FlashCardState, FlipFlashCard};
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Card {
     #[serde(skip)]
     state: FlashCardState,
     front: String,
     back: String,
     hint: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
 }
 
 impl Card {
@@ -19,9 +21,29 @@ impl Card {
             front,
             back,
             hint: Some(hint),
+            id: None,
             state: FlashCardState::Front,
         }
     }
+
+    pub fn set_id(&mut self, id: String) {
+        self.id = Some(id);
+    }
+
+    pub fn set_hint(&mut self, hint: Option<String>) {
+        self.hint = hint;
+    }
+}
+
+// FNV-1a hash of the card's text, so cards without an explicit id still get
+// an identifier that is stable across runs and Rust versions.
+pub fn content_id(front: &str, back: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in front.bytes().chain([0x1f]).chain(back.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
 }
 
 impl Display for Card {
@@ -52,6 +74,13 @@ impl FlashCard<'_> for Card {
     fn get_hint(&self) -> Option<String> {
         self.hint.clone()
     }
+
+    fn get_id(&self) -> String {
+        match &self.id {
+            Some(id) => id.to_string(),
+            None => content_id(&self.front, &self.back),
+        }
+    }
 }
 
 impl FlipFlashCard for Card {
@@ -133,4 +162,27 @@ mod tests {
         card.set_state(FlashCardState::Hint);
         assert_eq!(format!("{}", card), hint);
     }
+
+    #[test]
+    fn test_get_id() {
+        let mut card = Card::new("front".to_string(), "back".to_string(), "hint".to_string());
+        let other = Card::new("front".to_string(), "back".to_string(), "other".to_string());
+        let different = Card::new("back".to_string(), "front".to_string(), "hint".to_string());
+
+        assert_eq!(card.get_id(), "281e6d30d28f8222".to_string());
+        assert_eq!(card.get_id(), other.get_id());
+        assert_ne!(card.get_id(), different.get_id());
+
+        card.set_id("card-1".to_string());
+        assert_eq!(card.get_id(), "card-1".to_string());
+    }
+
+    #[test]
+    fn test_set_hint() {
+        let mut card = Card::new("front".to_string(), "back".to_string(), "hint".to_string());
+
+        card.set_hint(None);
+        assert_eq!(card.get_hint(), None);
+        assert_eq!(format!("{}", card), "front".to_string());
+    }
 }
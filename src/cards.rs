@@ -2,7 +2,9 @@ use std::collections::VecDeque;
 use std::fmt::Display;
 use std::iter::FromIterator;
 
-use rand::{thread_rng, Rng};
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 use crate::{FlashCard, FlashCards};
 
@@ -58,16 +60,8 @@ impl<T> FlashCards<T> for Cards<T>
 where
     T: for<'de> FlashCard<'de>,
 {
-    fn shuffle(&mut self) {
-        let mut rng = thread_rng();
-        let total_num_of_cards = self.data.len();
-
-        for _ in 0..total_num_of_cards {
-            let rand_num_1 = rng.gen_range(0..total_num_of_cards);
-            let rand_num_2 = rng.gen_range(0..total_num_of_cards);
-
-            self.data.swap(rand_num_1, rand_num_2);
-        }
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore) {
+        self.data.make_contiguous().shuffle(rng);
     }
 
     fn deck_size(&self) -> usize {
@@ -88,6 +82,15 @@ where
             self.add_card(deck.draw().unwrap());
         }
     }
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+}
+
+// ChaCha8 gives the same sequence on every platform and rand release, so a
+// seed can be shared to reproduce a shuffle elsewhere.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
 }
 
 impl<U> FromIterator<U> for Cards<U>
@@ -162,6 +165,36 @@ mod tests {
         assert_eq!(10, cards.deck_size())
     }
 
+    #[test]
+    fn test_flashcards_shuffle_with_seeded_rng() {
+        let mut cards_1 = create_test_cards();
+        let mut cards_2 = create_test_cards();
+
+        cards_1.shuffle_with_rng(&mut seeded_rng(42));
+        cards_2.shuffle_with_rng(&mut seeded_rng(42));
+
+        assert_eq!(format!("{}", cards_1), format!("{}", cards_2));
+        assert_ne!(format!("{}", cards_1), format!("{}", create_test_cards()));
+    }
+
+    #[test]
+    fn test_flashcards_shuffle_is_uniform() {
+        let mut rng = seeded_rng(7);
+        let mut counts = std::collections::HashMap::new();
+
+        for _ in 0..6000 {
+            let mut cards: Cards<Card> = (0..3)
+                .map(|x| Card::new(x.to_string(), x.to_string(), String::new()))
+                .collect();
+            cards.shuffle_with_rng(&mut rng);
+            *counts.entry(format!("{}", cards)).or_insert(0) += 1;
+        }
+
+        // Each of the six orders should turn up about 1000 times.
+        assert_eq!(6, counts.len());
+        assert!(counts.values().all(|count| (850..1150).contains(count)));
+    }
+
     #[test]
     fn test_flashcards_empty_print() {
         let cards: Cards<Card> = Cards::new();
@@ -205,4 +238,15 @@ mod tests {
 
         assert!(found_difference)
     }
+
+    #[test]
+    fn test_flashcards_iter() {
+        let cards = create_test_cards();
+
+        let fronts: Vec<String> = cards.iter().map(|card| card.get_front()).collect();
+
+        assert_eq!(10, fronts.len());
+        assert_eq!("0 - front".to_string(), fronts[0]);
+        assert_eq!(10, cards.deck_size());
+    }
 }
@@ -0,0 +1,204 @@
+use rand::seq::SliceRandom;
+use rand::{thread_rng, RngCore};
+
+use crate::traits::FlashCardsManager;
+use crate::{CardsManager, FlipFlashCard, Grade, Scheduler};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultipleChoice {
+    pub question: String,
+    pub options: Vec<String>,
+    pub answer: usize,
+}
+
+impl MultipleChoice {
+    // Builds a question for the current card with up to `distractors` wrong
+    // options taken from the backs of the other cards in the manager, or
+    // from their fronts when the card is studied in reverse.
+    pub fn create_from_manager<T, S>(
+        manager: &mut CardsManager<T, S>,
+        distractors: usize,
+    ) -> Option<Self>
+    where
+        T: FlipFlashCard,
+        S: Scheduler,
+    {
+        Self::create_from_manager_with_rng(manager, distractors, &mut thread_rng())
+    }
+
+    pub fn create_from_manager_with_rng<T, S>(
+        manager: &mut CardsManager<T, S>,
+        distractors: usize,
+        rng: &mut dyn RngCore,
+    ) -> Option<Self>
+    where
+        T: FlipFlashCard,
+        S: Scheduler,
+    {
+        let reversed = manager.is_current_card_reversed();
+        let sides = |card: &T| match reversed {
+            true => (card.get_back(), card.get_front()),
+            false => (card.get_front(), card.get_back()),
+        };
+        let card = manager.current_card()?.upgrade()?;
+        let (question, correct) = sides(&*card.try_borrow().ok()?);
+
+        let mut candidates: Vec<String> = Vec::new();
+        for other in manager.cards().filter_map(|other| other.upgrade()) {
+            let option = match other.try_borrow() {
+                Ok(other) => sides(&other).1,
+                Err(_) => continue,
+            };
+            if !same_option(&option, &correct)
+                && !candidates
+                    .iter()
+                    .any(|candidate| same_option(candidate, &option))
+            {
+                candidates.push(option);
+            }
+        }
+
+        let mut options: Vec<String> = candidates
+            .choose_multiple(rng, distractors)
+            .cloned()
+            .collect();
+        options.push(correct.clone());
+        options.shuffle(rng);
+        let answer = options.iter().position(|option| *option == correct)?;
+
+        Some(MultipleChoice {
+            question,
+            options,
+            answer,
+        })
+    }
+
+    pub fn is_correct(&self, choice: usize) -> bool {
+        choice == self.answer
+    }
+
+    // Grades the current card Good for the right option and Again otherwise.
+    pub fn answer_current_card<T: FlipFlashCard>(
+        &self,
+        manager: &mut dyn FlashCardsManager<T>,
+        choice: usize,
+    ) -> bool {
+        let correct = self.is_correct(choice);
+        let grade = match correct {
+            true => Grade::Good,
+            false => Grade::Again,
+        };
+
+        manager.grade_current_card(grade);
+        correct
+    }
+}
+
+// Options that only differ in case or surrounding whitespace would give the
+// answer away, so they count as the same option.
+fn same_option(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::seeded_rng;
+    use crate::{Card, Cards, StudyDirection};
+
+    fn create_test_manager(rows: &[(&str, &str)]) -> CardsManager<Card> {
+        let cards: Cards<Card> = rows
+            .iter()
+            .map(|(front, back)| Card::new(front.to_string(), back.to_string(), String::new()))
+            .collect();
+
+        CardsManager::create_from_deck(cards)
+    }
+
+    #[test]
+    fn test_multiple_choice() {
+        let mut manager = create_test_manager(&[
+            ("France", "Paris"),
+            ("Spain", "Madrid"),
+            ("Italy", "Rome"),
+            ("Germany", "Berlin"),
+            ("Portugal", "Lisbon"),
+        ]);
+        manager.next_card();
+        let mut rng = seeded_rng(7);
+
+        let choice =
+            MultipleChoice::create_from_manager_with_rng(&mut manager, 3, &mut rng).unwrap();
+
+        assert_eq!(choice.question, "France".to_string());
+        assert_eq!(choice.options.len(), 4);
+        assert_eq!(choice.options[choice.answer], "Paris".to_string());
+        assert_eq!(
+            choice
+                .options
+                .iter()
+                .filter(|option| *option == "Paris")
+                .count(),
+            1
+        );
+        assert!(choice.is_correct(choice.answer));
+        assert!(!choice.is_correct((choice.answer + 1) % 4));
+    }
+
+    #[test]
+    fn test_multiple_choice_in_reverse() {
+        let mut manager =
+            create_test_manager(&[("France", "Paris"), ("Spain", "Madrid"), ("Italy", "Rome")]);
+        manager.set_study_direction(StudyDirection::Reverse);
+        manager.next_card();
+
+        let choice = MultipleChoice::create_from_manager(&mut manager, 2).unwrap();
+
+        assert_eq!(choice.question, "Paris".to_string());
+        assert_eq!(choice.options[choice.answer], "France".to_string());
+        let mut options = choice.options.clone();
+        options.sort();
+        assert_eq!(
+            options,
+            vec![
+                "France".to_string(),
+                "Italy".to_string(),
+                "Spain".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multiple_choice_skips_duplicate_options() {
+        let mut manager = create_test_manager(&[
+            ("France", "Paris"),
+            ("Capital of France", "paris "),
+            ("Spain", "Madrid"),
+            ("Capital of Spain", "Madrid"),
+        ]);
+        manager.next_card();
+
+        let choice = MultipleChoice::create_from_manager(&mut manager, 5).unwrap();
+
+        let mut options = choice.options.clone();
+        options.sort();
+        assert_eq!(options, vec!["Madrid".to_string(), "Paris".to_string()]);
+    }
+
+    #[test]
+    fn test_multiple_choice_without_current_card() {
+        let mut manager = create_test_manager(&[("France", "Paris")]);
+
+        assert_eq!(MultipleChoice::create_from_manager(&mut manager, 3), None);
+    }
+
+    #[test]
+    fn test_answer_current_card() {
+        let mut manager = create_test_manager(&[("France", "Paris"), ("Spain", "Madrid")]);
+        manager.next_card();
+        let choice = MultipleChoice::create_from_manager(&mut manager, 1).unwrap();
+
+        assert!(choice.answer_current_card(&mut manager, choice.answer));
+        assert_eq!(manager.current_card_grade(), Some(Grade::Good));
+    }
+}
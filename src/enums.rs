@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq, PartialOrd, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
 pub enum FlashCardState {
     #[default]
     Front,
@@ -18,6 +21,35 @@ impl Display for FlashCardState {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Grade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Grade::Again => write!(f, "again"),
+            Grade::Hard => write!(f, "hard"),
+            Grade::Good => write!(f, "good"),
+            Grade::Easy => write!(f, "easy"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum StudyDirection {
+    #[default]
+    Forward,
+    Reverse,
+    Both,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,4 +60,12 @@ mod tests {
         assert_eq!("hint".to_string(), FlashCardState::Hint.to_string());
         assert_eq!("front".to_string(), FlashCardState::Front.to_string());
     }
+
+    #[test]
+    fn test_grade_display() {
+        assert_eq!("again".to_string(), Grade::Again.to_string());
+        assert_eq!("hard".to_string(), Grade::Hard.to_string());
+        assert_eq!("good".to_string(), Grade::Good.to_string());
+        assert_eq!("easy".to_string(), Grade::Easy.to_string());
+    }
 }
@@ -0,0 +1,270 @@
+use std::fmt::Display;
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Position {
+    pub line: Option<u64>,
+    pub column: Option<u64>,
+    pub record: Option<u64>,
+}
+
+impl Position {
+    pub fn line(line: u64) -> Self {
+        Position {
+            line: Some(line),
+            ..Default::default()
+        }
+    }
+
+    pub fn record(record: u64) -> Self {
+        Position {
+            record: Some(record),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.line.is_none() && self.column.is_none() && self.record.is_none()
+    }
+}
+
+impl From<&csv::Position> for Position {
+    fn from(position: &csv::Position) -> Self {
+        Position {
+            line: Some(position.line()),
+            column: None,
+            record: Some(position.record()),
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column)),
+            self.record.map(|record| format!("record {}", record)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Malformed { position: Position, message: String },
+    MissingColumn { position: Position, column: String },
+    EmptyField { position: Position, field: String },
+    Encoding { position: Position },
+    DuplicateId { position: Position, id: String },
+    Io(std::io::Error),
+}
+
+impl LoadError {
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            LoadError::Malformed { position, .. }
+            | LoadError::MissingColumn { position, .. }
+            | LoadError::EmptyField { position, .. }
+            | LoadError::Encoding { position }
+            | LoadError::DuplicateId { position, .. } => Some(position),
+            LoadError::Io(_) => None,
+        }
+    }
+
+    // Turns a serde error for a single record into a LoadError, picking out
+    // the "missing field" case so it can be reported as a missing column.
+    pub(crate) fn from_message(position: Position, message: String) -> Self {
+        let column = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next());
+
+        match column {
+            Some(column) => LoadError::MissingColumn {
+                position,
+                column: column.to_string(),
+            },
+            None => LoadError::Malformed { position, message },
+        }
+    }
+
+    pub(crate) fn from_csv(err: csv::Error, fallback: Position) -> Self {
+        let position = err.position().map(Position::from).unwrap_or(fallback);
+        let message = err.to_string();
+        // CSV columns are the record's fields, numbered from 1.
+        let in_field = |field: Option<u64>| Position {
+            column: field.map(|field| field + 1),
+            ..position
+        };
+
+        match err.into_kind() {
+            csv::ErrorKind::Io(err) => LoadError::Io(err),
+            csv::ErrorKind::Utf8 { err, .. } => LoadError::Encoding {
+                position: in_field(Some(err.field() as u64)),
+            },
+            csv::ErrorKind::Deserialize { err, .. } => {
+                let position = in_field(err.field());
+                match err.kind() {
+                    csv::DeserializeErrorKind::Message(message) => {
+                        LoadError::from_message(position, message.to_string())
+                    }
+                    _ => LoadError::Malformed {
+                        position,
+                        message: err.to_string(),
+                    },
+                }
+            }
+            _ => LoadError::Malformed { position, message },
+        }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(position) = self.position().filter(|position| !position.is_unknown()) {
+            write!(f, "{}: ", position)?;
+        }
+
+        match self {
+            LoadError::Malformed { message, .. } => write!(f, "{}", message),
+            LoadError::MissingColumn { column, .. } => {
+                write!(f, "missing required column `{}`", column)
+            }
+            LoadError::EmptyField { field, .. } => write!(f, "`{}` is empty", field),
+            LoadError::Encoding { .. } => write!(f, "invalid UTF-8"),
+            LoadError::DuplicateId { id, .. } => write!(f, "duplicate card id `{}`", id),
+            LoadError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+impl From<LoadError> for std::io::Error {
+    fn from(err: LoadError) -> Self {
+        match err {
+            LoadError::Io(err) => err,
+            err => std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+        }
+    }
+}
+
+// Returned when a snapshot does not match the deck it is restored against.
+#[derive(Debug, PartialEq)]
+pub enum SnapshotError {
+    UnknownCard { id: String },
+    MissingCard { id: String },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::UnknownCard { id } => {
+                write!(f, "snapshot has card `{}` which is not in the deck", id)
+            }
+            SnapshotError::MissingCard { id } => {
+                write!(f, "deck has card `{}` which is not in the snapshot", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_display() {
+        assert_eq!("line 3".to_string(), Position::line(3).to_string());
+        assert_eq!("record 2".to_string(), Position::record(2).to_string());
+
+        let position = Position {
+            line: Some(3),
+            column: Some(7),
+            record: Some(2),
+        };
+        assert_eq!(
+            "line 3, column 7, record 2".to_string(),
+            position.to_string()
+        );
+    }
+
+    #[test]
+    fn test_load_error_display() {
+        let err = LoadError::EmptyField {
+            position: Position::line(4),
+            field: "back".to_string(),
+        };
+        assert_eq!("line 4: `back` is empty".to_string(), err.to_string());
+
+        let err = LoadError::DuplicateId {
+            position: Position::default(),
+            id: "card-1".to_string(),
+        };
+        assert_eq!("duplicate card id `card-1`".to_string(), err.to_string());
+    }
+
+    #[test]
+    fn test_from_message() {
+        let err = LoadError::from_message(Position::record(1), "missing field `back`".to_string());
+        assert!(matches!(err, LoadError::MissingColumn { column, .. } if column == "back"));
+
+        let err = LoadError::from_message(Position::record(1), "invalid type".to_string());
+        assert!(matches!(err, LoadError::Malformed { .. }));
+    }
+
+    #[test]
+    fn test_from_csv_column() {
+        let mut rdr = csv::Reader::from_reader("front,count\nhola,many\n".as_bytes());
+        let headers = rdr.headers().unwrap().clone();
+        let record = rdr.records().next().unwrap().unwrap();
+        let err = record
+            .deserialize::<(String, u32)>(Some(&headers))
+            .err()
+            .unwrap();
+
+        let err = LoadError::from_csv(err, Position::default());
+        assert!(matches!(err, LoadError::Malformed { .. }));
+        let position = err.position().unwrap();
+        assert_eq!((position.line, position.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn test_snapshot_error_display() {
+        let err = SnapshotError::UnknownCard {
+            id: "card-1".to_string(),
+        };
+        assert_eq!(
+            "snapshot has card `card-1` which is not in the deck".to_string(),
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let err: std::io::Error = LoadError::Encoding {
+            position: Position::line(1),
+        }
+        .into();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 1: invalid UTF-8");
+    }
+}
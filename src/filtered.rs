@@ -0,0 +1,194 @@
+use std::cell::RefCell;
+use std::rc::Weak;
+
+use rand::RngCore;
+
+use crate::scheduler::Unscheduled;
+use crate::traits::{FlashCardsManager, FlipFlashCard, Scheduler};
+use crate::{Cards, CardsManager, FlashCards, Grade, SessionStats};
+
+// A CardsManager over the cards of a deck that pass a filter, such as one
+// tag or the cards containing a word.
+pub struct FilteredCardsManager<T, S = Unscheduled>
+where
+    T: FlipFlashCard,
+    S: Scheduler,
+{
+    manager: CardsManager<T, S>,
+    num_of_cards_excluded: usize,
+}
+
+impl<T> FilteredCardsManager<T>
+where
+    T: FlipFlashCard,
+{
+    pub fn create_from_deck(deck: impl FlashCards<T>, filter: impl Fn(&T) -> bool) -> Self {
+        Self::create_scheduled_from_deck(deck, Unscheduled {}, filter)
+    }
+}
+
+impl<T, S> FilteredCardsManager<T, S>
+where
+    T: FlipFlashCard,
+    S: Scheduler,
+{
+    pub fn create_scheduled_from_deck(
+        mut deck: impl FlashCards<T>,
+        scheduler: S,
+        filter: impl Fn(&T) -> bool,
+    ) -> Self {
+        let mut cards = Cards::new();
+        let mut num_of_cards_excluded = 0;
+        while let Some(card) = deck.draw() {
+            match filter(&card) {
+                true => cards.add_card(card),
+                false => num_of_cards_excluded += 1,
+            }
+        }
+
+        FilteredCardsManager {
+            manager: CardsManager::create_scheduled_from_deck(cards, scheduler),
+            num_of_cards_excluded,
+        }
+    }
+
+    pub fn num_of_cards_excluded(&self) -> usize {
+        self.num_of_cards_excluded
+    }
+
+    pub fn manager(&self) -> &CardsManager<T, S> {
+        &self.manager
+    }
+
+    pub fn manager_mut(&mut self) -> &mut CardsManager<T, S> {
+        &mut self.manager
+    }
+}
+
+impl<T, S> FlashCardsManager<T> for FilteredCardsManager<T, S>
+where
+    T: FlipFlashCard,
+    S: Scheduler,
+{
+    fn next_card(&mut self) -> Option<Weak<RefCell<T>>> {
+        self.manager.next_card()
+    }
+
+    fn current_card(&mut self) -> Option<Weak<RefCell<T>>> {
+        self.manager.current_card()
+    }
+
+    fn flip_current_card(&mut self) {
+        self.manager.flip_current_card()
+    }
+
+    fn try_to_flip_current_card_to_hint(&mut self) {
+        self.manager.try_to_flip_current_card_to_hint()
+    }
+
+    fn reset_current_card_state(&mut self) {
+        self.manager.reset_current_card_state()
+    }
+
+    fn previous_card(&mut self) -> Option<Weak<RefCell<T>>> {
+        self.manager.previous_card()
+    }
+
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore) {
+        self.manager.shuffle_with_rng(rng)
+    }
+
+    fn add_previous_cards_to_deck(&mut self) {
+        self.manager.add_previous_cards_to_deck()
+    }
+
+    fn num_of_cards_in_deck(&self) -> usize {
+        self.manager.num_of_cards_in_deck()
+    }
+
+    fn num_of_cards_seen(&self) -> usize {
+        self.manager.num_of_cards_seen()
+    }
+
+    fn grade_current_card(&mut self, grade: Grade) {
+        self.manager.grade_current_card(grade)
+    }
+
+    fn current_card_grade(&self) -> Option<Grade> {
+        self.manager.current_card_grade()
+    }
+
+    fn is_current_card_reversed(&self) -> bool {
+        self.manager.is_current_card_reversed()
+    }
+
+    fn stats(&self) -> &SessionStats {
+        self.manager.stats()
+    }
+
+    fn finish_session(&mut self) {
+        self.manager.finish_session()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::tests::create_test_cards;
+    use crate::{Card, FlashCard, Sm2};
+
+    fn front(card: Option<Weak<RefCell<Card>>>) -> String {
+        card.unwrap().upgrade().unwrap().borrow().get_front()
+    }
+
+    #[test]
+    fn test_filtered_manager() {
+        let mut card_manager =
+            FilteredCardsManager::create_from_deck(create_test_cards(10), |card| {
+                card.get_front().starts_with('3') || card.get_front().starts_with('7')
+            });
+
+        assert_eq!(8, card_manager.num_of_cards_excluded());
+        assert_eq!(2, card_manager.num_of_cards_in_deck());
+        assert_eq!("3 - front".to_string(), front(card_manager.next_card()));
+        assert_eq!("7 - front".to_string(), front(card_manager.next_card()));
+        assert!(card_manager.next_card().is_none());
+
+        card_manager.grade_current_card(Grade::Good);
+        assert_eq!(1, card_manager.manager().review_log().len());
+    }
+
+    #[test]
+    fn test_managers_behind_trait_objects() {
+        let mut managers: Vec<Box<dyn FlashCardsManager<Card>>> = vec![
+            Box::new(CardsManager::create_from_deck(create_test_cards(10))),
+            Box::new(CardsManager::create_scheduled_from_deck(
+                create_test_cards(10),
+                Sm2::new(),
+            )),
+            Box::new(FilteredCardsManager::create_from_deck(
+                create_test_cards(10),
+                |card| card.get_front() != "0 - front",
+            )),
+        ];
+
+        let fronts: Vec<String> = managers
+            .iter_mut()
+            .map(|card_manager| front(card_manager.next_card()))
+            .collect();
+        assert_eq!(
+            vec![
+                "0 - front".to_string(),
+                "0 - front".to_string(),
+                "1 - front".to_string()
+            ],
+            fronts
+        );
+
+        // A boxed manager is itself a FlashCardsManager.
+        fn count_cards(card_manager: &impl FlashCardsManager<Card>) -> usize {
+            card_manager.num_of_cards_seen() + card_manager.num_of_cards_in_deck()
+        }
+        assert_eq!(9, count_cards(&managers.pop().unwrap()));
+    }
+}
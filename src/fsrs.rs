@@ -0,0 +1,257 @@
+use std::time::{Duration, SystemTime};
+
+use crate::enums::Grade;
+use crate::scheduler::SECONDS_PER_DAY;
+use crate::traits::Scheduler;
+
+const DECAY: f64 = -0.5;
+const FACTOR: f64 = 19.0 / 81.0;
+const MINIMUM_DIFFICULTY: f64 = 1.0;
+const MAXIMUM_DIFFICULTY: f64 = 10.0;
+
+// Default FSRS-4.5 parameters.
+pub const DEFAULT_WEIGHTS: [f64; 17] = [
+    0.4872, 1.4003, 3.7145, 13.8206, 5.1618, 1.2298, 0.8975, 0.031, 1.6474, 0.1367, 1.0461, 2.1072,
+    0.0793, 0.3246, 1.587, 0.2272, 2.8755,
+];
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct FsrsState {
+    pub stability: f64,
+    pub difficulty: f64,
+    pub repetitions: u32,
+    pub last_review: Option<SystemTime>,
+    pub due: Option<SystemTime>,
+}
+
+impl FsrsState {
+    // Probability of recalling the card after `elapsed_days` days.
+    pub fn retrievability(&self, elapsed_days: f64) -> f64 {
+        (1.0 + FACTOR * elapsed_days / self.stability).powf(DECAY)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fsrs {
+    weights: [f64; 17],
+    desired_retention: f64,
+    maximum_interval: u32,
+}
+
+impl Fsrs {
+    pub fn new(desired_retention: f64) -> Self {
+        Fsrs::with_weights(DEFAULT_WEIGHTS, desired_retention)
+    }
+
+    pub fn with_weights(weights: [f64; 17], desired_retention: f64) -> Self {
+        Fsrs {
+            weights,
+            desired_retention,
+            maximum_interval: 36500,
+        }
+    }
+
+    pub fn desired_retention(&self) -> f64 {
+        self.desired_retention
+    }
+
+    fn rating(grade: Grade) -> f64 {
+        match grade {
+            Grade::Again => 1.0,
+            Grade::Hard => 2.0,
+            Grade::Good => 3.0,
+            Grade::Easy => 4.0,
+        }
+    }
+
+    fn initial_stability(&self, grade: Grade) -> f64 {
+        self.weights[Fsrs::rating(grade) as usize - 1]
+    }
+
+    fn initial_difficulty(&self, grade: Grade) -> f64 {
+        let difficulty = self.weights[4] - (Fsrs::rating(grade) - 3.0) * self.weights[5];
+        difficulty.clamp(MINIMUM_DIFFICULTY, MAXIMUM_DIFFICULTY)
+    }
+
+    fn next_difficulty(&self, difficulty: f64, grade: Grade) -> f64 {
+        let difficulty = difficulty - self.weights[6] * (Fsrs::rating(grade) - 3.0);
+        // Mean reversion towards the difficulty of a first "good" answer.
+        let difficulty = self.weights[7] * self.initial_difficulty(Grade::Good)
+            + (1.0 - self.weights[7]) * difficulty;
+        difficulty.clamp(MINIMUM_DIFFICULTY, MAXIMUM_DIFFICULTY)
+    }
+
+    fn next_recall_stability(&self, state: &FsrsState, retrievability: f64, grade: Grade) -> f64 {
+        let hard_penalty = match grade {
+            Grade::Hard => self.weights[15],
+            _ => 1.0,
+        };
+        let easy_bonus = match grade {
+            Grade::Easy => self.weights[16],
+            _ => 1.0,
+        };
+
+        state.stability
+            * (self.weights[8].exp()
+                * (11.0 - state.difficulty)
+                * state.stability.powf(-self.weights[9])
+                * ((self.weights[10] * (1.0 - retrievability)).exp() - 1.0)
+                * hard_penalty
+                * easy_bonus
+                + 1.0)
+    }
+
+    fn next_forget_stability(&self, state: &FsrsState, retrievability: f64) -> f64 {
+        self.weights[11]
+            * state.difficulty.powf(-self.weights[12])
+            * ((state.stability + 1.0).powf(self.weights[13]) - 1.0)
+            * (self.weights[14] * (1.0 - retrievability)).exp()
+    }
+
+    // Number of days until the chance of recall drops to the desired retention.
+    pub fn next_interval(&self, stability: f64) -> u32 {
+        let interval = stability / FACTOR * (self.desired_retention.powf(1.0 / DECAY) - 1.0);
+        (interval.round() as u32).clamp(1, self.maximum_interval)
+    }
+}
+
+impl Default for Fsrs {
+    fn default() -> Self {
+        Fsrs::new(0.9)
+    }
+}
+
+impl Scheduler for Fsrs {
+    type State = FsrsState;
+
+    fn review(&self, state: &FsrsState, grade: Grade, now: SystemTime) -> FsrsState {
+        let (stability, difficulty) = match state.last_review {
+            Some(last_review) if state.repetitions > 0 => {
+                let elapsed_days = now
+                    .duration_since(last_review)
+                    .unwrap_or_default()
+                    .as_secs()
+                    / SECONDS_PER_DAY;
+                let retrievability = state.retrievability(elapsed_days as f64);
+
+                let stability = match grade {
+                    Grade::Again => self.next_forget_stability(state, retrievability),
+                    _ => self.next_recall_stability(state, retrievability, grade),
+                };
+                (stability, self.next_difficulty(state.difficulty, grade))
+            }
+            _ => (
+                self.initial_stability(grade),
+                self.initial_difficulty(grade),
+            ),
+        };
+
+        let interval = self.next_interval(stability);
+
+        FsrsState {
+            stability,
+            difficulty,
+            repetitions: state.repetitions + 1,
+            last_review: Some(now),
+            due: Some(now + Duration::from_secs(interval as u64 * SECONDS_PER_DAY)),
+        }
+    }
+
+    fn due(&self, state: &FsrsState) -> Option<SystemTime> {
+        state.due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(num: u64) -> Duration {
+        Duration::from_secs(num * SECONDS_PER_DAY)
+    }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-6,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_first_review() {
+        let scheduler = Fsrs::default();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let again = scheduler.review(&FsrsState::default(), Grade::Again, now);
+        assert_close(0.4872, again.stability);
+        assert_close(7.6214, again.difficulty);
+        assert_eq!(again.due, Some(now + days(1)));
+
+        let good = scheduler.review(&FsrsState::default(), Grade::Good, now);
+        assert_close(3.7145, good.stability);
+        assert_close(5.1618, good.difficulty);
+        assert_eq!(good.due, Some(now + days(4)));
+
+        let easy = scheduler.review(&FsrsState::default(), Grade::Easy, now);
+        assert_eq!(easy.due, Some(now + days(14)));
+    }
+
+    #[test]
+    fn test_good_review_sequence() {
+        let scheduler = Fsrs::default();
+        let mut now = SystemTime::UNIX_EPOCH;
+        let mut state = FsrsState::default();
+
+        let expected = [
+            (3.7145, 4),
+            (14.808100506496405, 15),
+            (49.46160494627206, 49),
+            (145.67063220768378, 146),
+        ];
+
+        for (stability, interval) in expected {
+            state = scheduler.review(&state, Grade::Good, now);
+            assert_close(stability, state.stability);
+            assert_close(5.1618, state.difficulty);
+
+            now += days(interval);
+            assert_eq!(state.due, Some(now));
+        }
+        assert_eq!(state.repetitions, 4);
+    }
+
+    #[test]
+    fn test_lapse_after_review() {
+        let scheduler = Fsrs::default();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let state = scheduler.review(&FsrsState::default(), Grade::Good, now);
+        let state = scheduler.review(&state, Grade::Again, now + days(4));
+
+        assert_close(1.4332344897795595, state.stability);
+        assert_close(6.901155, state.difficulty);
+        assert_eq!(state.due, Some(now + days(5)));
+    }
+
+    #[test]
+    fn test_desired_retention() {
+        let scheduler = Fsrs::new(0.8);
+
+        assert_eq!(scheduler.desired_retention(), 0.8);
+        assert_eq!(scheduler.next_interval(3.7145), 9);
+        assert_eq!(Fsrs::default().next_interval(3.7145), 4);
+    }
+
+    #[test]
+    fn test_retrievability() {
+        let state = FsrsState {
+            stability: 10.0,
+            ..Default::default()
+        };
+
+        assert_close(1.0, state.retrievability(0.0));
+        assert_close(0.9, state.retrievability(10.0));
+    }
+}
@@ -0,0 +1,205 @@
+use std::fs::OpenOptions;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{FlashCardState, Grade};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Review {
+    pub card_id: String,
+    pub reviewed_at: u64,
+    pub grade: Grade,
+    pub time_taken_ms: u64,
+    pub state: FlashCardState,
+}
+
+impl Review {
+    pub fn new(
+        card_id: String,
+        reviewed_at: SystemTime,
+        grade: Grade,
+        time_taken: Duration,
+        state: FlashCardState,
+    ) -> Self {
+        Review {
+            card_id,
+            reviewed_at: reviewed_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            grade,
+            time_taken_ms: time_taken.as_millis() as u64,
+            state,
+        }
+    }
+
+    pub fn reviewed_at(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.reviewed_at)
+    }
+
+    pub fn time_taken(&self) -> Duration {
+        Duration::from_millis(self.time_taken_ms)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReviewLog {
+    reviews: Vec<Review>,
+    // Number of reviews that are already stored in the log file.
+    persisted: usize,
+}
+
+impl ReviewLog {
+    pub fn new() -> Self {
+        ReviewLog {
+            reviews: Vec::new(),
+            persisted: 0,
+        }
+    }
+
+    pub fn record(&mut self, review: Review) {
+        self.reviews.push(review);
+    }
+
+    pub fn reviews(&self) -> &[Review] {
+        &self.reviews
+    }
+
+    pub fn reviews_for<'a>(&'a self, card_id: &'a str) -> impl Iterator<Item = &'a Review> {
+        self.reviews
+            .iter()
+            .filter(move |review| review.card_id == card_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.reviews.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reviews.is_empty()
+    }
+
+    pub fn load(reader: impl std::io::Read) -> Result<Self, Error> {
+        let mut rdr = csv::Reader::from_reader(reader);
+        let mut log = ReviewLog::new();
+
+        for result in rdr.deserialize() {
+            let review: Review = result?;
+            log.record(review);
+        }
+
+        log.persisted = log.len();
+        Ok(log)
+    }
+
+    pub fn write(&self, writer: impl std::io::Write) -> Result<(), Error> {
+        let mut wtr = csv::Writer::from_writer(writer);
+
+        for review in &self.reviews {
+            wtr.serialize(review)?;
+        }
+
+        wtr.flush()
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        match std::fs::File::open(path) {
+            Ok(file) => ReviewLog::load(file),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(ReviewLog::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Appends the reviews recorded since the last save, leaving earlier
+    // entries in the file untouched.
+    pub fn append_to_file(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let is_empty = file.metadata()?.len() == 0;
+
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(is_empty)
+            .from_writer(file);
+
+        for review in &self.reviews[self.persisted..] {
+            wtr.serialize(review)?;
+        }
+        wtr.flush()?;
+
+        self.persisted = self.reviews.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_review(card_id: &str, grade: Grade) -> Review {
+        Review::new(
+            card_id.to_string(),
+            SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+            grade,
+            Duration::from_millis(1500),
+            FlashCardState::Back,
+        )
+    }
+
+    #[test]
+    fn test_write_and_load() {
+        let mut log = ReviewLog::new();
+        log.record(create_test_review("card_1", Grade::Good));
+        log.record(create_test_review("card_2", Grade::Again));
+
+        let mut buffer = Vec::new();
+        log.write(&mut buffer).unwrap();
+
+        let data = String::from_utf8(buffer.clone()).unwrap();
+        assert!(data.starts_with("card_id,reviewed_at,grade,time_taken_ms,state\n"));
+        assert!(data.contains("card_2,60,again,1500,back"));
+
+        let loaded = ReviewLog::load(buffer.as_slice()).unwrap();
+        assert_eq!(log.reviews(), loaded.reviews());
+        assert_eq!(
+            loaded.reviews()[0].time_taken(),
+            Duration::from_millis(1500)
+        );
+    }
+
+    #[test]
+    fn test_reviews_for() {
+        let mut log = ReviewLog::new();
+        log.record(create_test_review("card_1", Grade::Good));
+        log.record(create_test_review("card_2", Grade::Again));
+        log.record(create_test_review("card_1", Grade::Easy));
+
+        let grades: Vec<Grade> = log.reviews_for("card_1").map(|r| r.grade).collect();
+        assert_eq!(grades, vec![Grade::Good, Grade::Easy]);
+    }
+
+    #[test]
+    fn test_append_to_file() {
+        let path =
+            std::env::temp_dir().join(format!("flash_cards_review_log_{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut log = ReviewLog::open(&path).unwrap();
+        assert!(log.is_empty());
+        log.record(create_test_review("card_1", Grade::Good));
+        log.append_to_file(&path).unwrap();
+
+        let mut log = ReviewLog::open(&path).unwrap();
+        assert_eq!(1, log.len());
+        log.record(create_test_review("card_2", Grade::Hard));
+        log.append_to_file(&path).unwrap();
+        log.append_to_file(&path).unwrap();
+
+        let log = ReviewLog::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(2, log.len());
+        assert_eq!(log.reviews()[1].grade, Grade::Hard);
+    }
+}
@@ -1,13 +1,50 @@
+#[cfg(feature = "anki")]
+pub mod anki;
 pub mod card;
 pub mod cards;
+pub mod choice;
 pub mod enums;
+pub mod errors;
+pub mod filtered;
+pub mod fsrs;
+pub mod history;
 pub mod loader;
 pub mod manager;
+pub mod quiz;
+pub mod scheduler;
+pub mod snapshot;
+pub mod stats;
+pub mod sync_manager;
+pub mod text;
 pub mod traits;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod validator;
 
+#[cfg(feature = "anki")]
+pub use anki::{Anki, AnkiDeck};
 pub use card::Card;
-pub use cards::Cards;
-pub use enums::FlashCardState;
-pub use loader::Csv;
-pub use manager::CardsManager;
-pub use traits::{FlashCard, FlashCards, FlipFlashCard, Loader};
+pub use cards::{seeded_rng, Cards};
+pub use choice::MultipleChoice;
+pub use enums::{FlashCardState, Grade, StudyDirection};
+pub use errors::{LoadError, Position, SnapshotError};
+pub use filtered::FilteredCardsManager;
+pub use fsrs::{Fsrs, FsrsState};
+pub use history::{Review, ReviewLog};
+#[cfg(feature = "toml")]
+pub use loader::Toml;
+#[cfg(feature = "yaml")]
+pub use loader::Yaml;
+pub use loader::{Csv, DeckInfo, Json, LoadReport};
+pub use manager::{CardHandle, CardsManager, GenericCardsManager};
+pub use quiz::{Answer, AnswerResult, Normalization, Quiz};
+pub use scheduler::{Sm2, Sm2State, Unscheduled};
+pub use snapshot::{CardSnapshot, Snapshot};
+pub use stats::{CardStats, SessionReport, SessionStats};
+pub use sync_manager::SyncCardsManager;
+pub use text::{Markdown, PlainText};
+pub use traits::{
+    CardCell, CardRef, FlashCard, FlashCards, FlashCardsManager, FlipFlashCard, Loader, Saver,
+    Scheduler,
+};
+pub use validator::{has_errors, Finding, Problem, Severity, Validator};
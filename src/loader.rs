@@ -1,22 +1,389 @@
+use std::collections::HashSet;
 use std::io::Error;
 
-use crate::{Cards, FlashCard, FlashCards, Loader};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::card::content_id;
+use crate::errors::{LoadError, Position};
+use crate::{Cards, FlashCard, FlashCards, Loader, Saver};
+
+// Rejects cards with an empty side, or that reuse the explicit id of an
+// earlier card. Cards that only share their content get the same content id,
+// those are left for the Validator to report.
+pub(crate) struct CardChecker {
+    ids: HashSet<String>,
+}
+
+impl CardChecker {
+    pub(crate) fn new() -> Self {
+        CardChecker {
+            ids: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn check<T>(&mut self, card: &T, position: Position) -> Result<(), LoadError>
+    where
+        T: for<'de> FlashCard<'de>,
+    {
+        for (field, text) in [("front", card.get_front()), ("back", card.get_back())] {
+            if text.trim().is_empty() {
+                return Err(LoadError::EmptyField {
+                    position,
+                    field: field.to_string(),
+                });
+            }
+        }
+
+        let id = match explicit_id(card) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+        if self.ids.contains(&id) {
+            return Err(LoadError::DuplicateId { position, id });
+        }
+        self.ids.insert(id);
+
+        Ok(())
+    }
+}
+
+// The card's id when it was given one, rather than the one made from its
+// content.
+pub(crate) fn explicit_id<T>(card: &T) -> Option<String>
+where
+    T: for<'de> FlashCard<'de>,
+{
+    let id = card.get_id();
+    match id == content_id(&card.get_front(), &card.get_back()) {
+        true => None,
+        false => Some(id),
+    }
+}
+
+pub(crate) fn read_text(mut reader: impl std::io::Read) -> Result<String, LoadError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    String::from_utf8(data).map_err(|err| {
+        let valid = &err.as_bytes()[..err.utf8_error().valid_up_to()];
+        let line = valid.iter().filter(|byte| **byte == b'\n').count() + 1;
+
+        LoadError::Encoding {
+            position: Position::line(line as u64),
+        }
+    })
+}
+
+// Parser messages carry their own "at line X column Y" suffix, which is
+// already part of the error's position.
+fn without_location(message: String) -> String {
+    match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message,
+    }
+}
+
+pub struct LoadReport<T> {
+    pub cards: Box<dyn FlashCards<T>>,
+    pub rejected: Vec<LoadError>,
+}
 
 pub struct Csv {}
 
+impl Csv {
+    // Reads every record, handing each bad one to `reject`. Returning an
+    // error from `reject` stops the load.
+    fn read_cards<T>(
+        reader: impl std::io::Read,
+        mut reject: impl FnMut(LoadError) -> Result<(), LoadError>,
+    ) -> Result<Cards<T>, LoadError>
+    where
+        T: for<'de> FlashCard<'de>,
+    {
+        let mut rdr = csv::Reader::from_reader(reader);
+        let headers = rdr
+            .headers()
+            .map_err(|err| LoadError::from_csv(err, Position::line(1)))?
+            .clone();
+        let mut record = csv::StringRecord::new();
+        let mut checker = CardChecker::new();
+        let mut cards: Cards<T> = Cards::new();
+
+        loop {
+            match rdr.read_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(err) => match LoadError::from_csv(err, Position::default()) {
+                    LoadError::Io(err) => return Err(LoadError::Io(err)),
+                    err => {
+                        reject(err)?;
+                        continue;
+                    }
+                },
+            }
+
+            let position = record.position().map(Position::from).unwrap_or_default();
+            let card = record
+                .deserialize::<T>(Some(&headers))
+                .map_err(|err| LoadError::from_csv(err, position))
+                .and_then(|card| checker.check(&card, position).map(|_| card));
+
+            match card {
+                Ok(card) => cards.add_card(card),
+                Err(err) => reject(err)?,
+            }
+        }
+
+        Ok(cards)
+    }
+
+    // Loads every record that can be read and reports the ones that could
+    // not, instead of stopping at the first bad record.
+    pub fn load_lenient<T>(reader: impl std::io::Read) -> Result<LoadReport<T>, LoadError>
+    where
+        T: for<'de> FlashCard<'de> + 'static,
+    {
+        let mut rejected = Vec::new();
+        let cards: Cards<T> = Csv::read_cards(reader, |err| {
+            rejected.push(err);
+            Ok(())
+        })?;
+
+        Ok(LoadReport {
+            cards: Box::new(cards),
+            rejected,
+        })
+    }
+}
+
 impl<T> Loader<T> for Csv
 where
     T: for<'de> FlashCard<'de> + 'static,
 {
-    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, Error> {
-        let mut rdr = csv::Reader::from_reader(reader);
-        let mut cards: Cards<T> = Cards::new();
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, LoadError> {
+        let cards: Cards<T> = Csv::read_cards(reader, Err)?;
+        Ok(Box::new(cards))
+    }
+}
 
-        for result in rdr.deserialize() {
-            let record: T = result?;
-            cards.add_card(record);
+impl<T> Saver<T> for Csv
+where
+    T: for<'de> FlashCard<'de> + Serialize,
+{
+    // Writes the same `front,back,hint` header the loader reads, even for an
+    // empty deck. The `id` column is only added when at least one card has an
+    // explicit id.
+    fn save(deck: &dyn FlashCards<T>, writer: impl std::io::Write) -> Result<(), Error> {
+        let with_id = deck.iter().any(|card| explicit_id(card).is_some());
+
+        let mut wtr = csv::Writer::from_writer(writer);
+        let mut headers = vec!["front", "back", "hint"];
+        if with_id {
+            headers.push("id");
+        }
+        wtr.write_record(&headers)?;
+
+        for card in deck.iter() {
+            let mut record = vec![
+                card.get_front(),
+                card.get_back(),
+                card.get_hint().unwrap_or_default(),
+            ];
+            if with_id {
+                record.push(explicit_id(card).unwrap_or_default());
+            }
+            wtr.write_record(&record)?;
         }
 
+        wtr.flush()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+pub struct DeckInfo {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+// A deck file is either a bare array of cards or an object that also
+// carries the deck's metadata.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DeckFile<T> {
+    Cards(Vec<T>),
+    Deck {
+        #[serde(flatten)]
+        info: DeckInfo,
+        cards: Vec<T>,
+    },
+}
+
+#[derive(Serialize)]
+struct DeckFileRef<'a, T> {
+    #[serde(flatten)]
+    info: &'a DeckInfo,
+    cards: Vec<&'a T>,
+}
+
+// Splits an already parsed document into its metadata and the raw value of
+// each card, so every card can be reported on by its record number.
+fn split_deck<'de, V>(document: V) -> Result<(DeckInfo, Vec<V>), LoadError>
+where
+    V: Deserializer<'de> + Deserialize<'de>,
+{
+    match DeckFile::<V>::deserialize(document) {
+        Ok(DeckFile::Cards(values)) => Ok((DeckInfo::default(), values)),
+        Ok(DeckFile::Deck { info, cards }) => Ok((info, cards)),
+        Err(_) => Err(LoadError::Malformed {
+            position: Position::default(),
+            message: "expected a list of cards or a deck with a `cards` list".to_string(),
+        }),
+    }
+}
+
+fn cards_from_values<'de, T, V>(values: Vec<V>) -> Result<Cards<T>, LoadError>
+where
+    T: for<'a> FlashCard<'a>,
+    V: Deserializer<'de>,
+{
+    let mut checker = CardChecker::new();
+    let mut cards: Cards<T> = Cards::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        let position = Position::record(index as u64 + 1);
+        let card = T::deserialize(value)
+            .map_err(|err| LoadError::from_message(position, err.to_string()))?;
+
+        checker.check(&card, position)?;
+        cards.add_card(card);
+    }
+
+    Ok(cards)
+}
+
+pub struct Json {}
+
+impl Json {
+    pub fn load_with_info<T>(
+        reader: impl std::io::Read,
+    ) -> Result<(DeckInfo, Box<dyn FlashCards<T>>), LoadError>
+    where
+        T: for<'de> FlashCard<'de> + 'static,
+    {
+        let document: serde_json::Value = serde_json::from_reader(reader).map_err(|err| {
+            if err.is_io() {
+                return LoadError::Io(err.into());
+            }
+            LoadError::Malformed {
+                position: Position {
+                    line: Some(err.line() as u64),
+                    column: Some(err.column() as u64),
+                    record: None,
+                },
+                message: without_location(err.to_string()),
+            }
+        })?;
+
+        let (info, values) = split_deck(document)?;
+        let cards: Cards<T> = cards_from_values(values)?;
+        Ok((info, Box::new(cards)))
+    }
+
+    pub fn save_with_info<T>(
+        info: &DeckInfo,
+        deck: &dyn FlashCards<T>,
+        writer: impl std::io::Write,
+    ) -> Result<(), Error>
+    where
+        T: for<'de> FlashCard<'de> + Serialize,
+    {
+        let deck = DeckFileRef {
+            info,
+            cards: deck.iter().collect(),
+        };
+
+        serde_json::to_writer_pretty(writer, &deck)?;
+        Ok(())
+    }
+}
+
+impl<T> Loader<T> for Json
+where
+    T: for<'de> FlashCard<'de> + 'static,
+{
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, LoadError> {
+        let (_, cards) = Json::load_with_info(reader)?;
+        Ok(cards)
+    }
+}
+
+impl<T> Saver<T> for Json
+where
+    T: for<'de> FlashCard<'de> + Serialize,
+{
+    fn save(deck: &dyn FlashCards<T>, writer: impl std::io::Write) -> Result<(), Error> {
+        let cards: Vec<&T> = deck.iter().collect();
+
+        serde_json::to_writer_pretty(writer, &cards)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml")]
+pub struct Toml {}
+
+#[cfg(feature = "toml")]
+impl<T> Loader<T> for Toml
+where
+    T: for<'de> FlashCard<'de> + 'static,
+{
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, LoadError> {
+        let data = read_text(reader)?;
+
+        let document: toml::Value = toml::from_str(&data).map_err(|err| {
+            let line = err
+                .span()
+                .map(|span| data[..span.start].matches('\n').count() as u64 + 1);
+            LoadError::Malformed {
+                position: Position {
+                    line,
+                    ..Default::default()
+                },
+                message: err.message().to_string(),
+            }
+        })?;
+
+        let (_, values) = split_deck(document)?;
+        let cards: Cards<T> = cards_from_values(values)?;
+        Ok(Box::new(cards))
+    }
+}
+
+#[cfg(feature = "yaml")]
+pub struct Yaml {}
+
+#[cfg(feature = "yaml")]
+impl<T> Loader<T> for Yaml
+where
+    T: for<'de> FlashCard<'de> + 'static,
+{
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, LoadError> {
+        let data = read_text(reader)?;
+
+        let document: serde_yaml::Value = serde_yaml::from_str(&data).map_err(|err| {
+            let location = err.location();
+            LoadError::Malformed {
+                position: Position {
+                    line: location.as_ref().map(|location| location.line() as u64),
+                    column: location.as_ref().map(|location| location.column() as u64),
+                    record: None,
+                },
+                message: without_location(err.to_string()),
+            }
+        })?;
+
+        let (_, values) = split_deck(document)?;
+        let cards: Cards<T> = cards_from_values(values)?;
         Ok(Box::new(cards))
     }
 }
@@ -26,6 +393,91 @@ mod tests {
     use super::*;
     use crate::Card;
 
+    fn load_csv(data: &str) -> Result<Box<dyn FlashCards<Card>>, LoadError> {
+        Csv::load(data.as_bytes())
+    }
+
+    #[test]
+    fn test_csv_lenient_reader() {
+        let data = b"\
+id,front,back,hint
+a,front_1,back_1,hint_1
+b,front_2,,hint_2
+c,front_3,back_3,hint_3,extra
+a,front_4,back_4,
+d,front_5,\xff,
+e,front_6,back_6,
+";
+        let mut report = Csv::load_lenient::<Card>(&data[..]).unwrap();
+
+        assert_eq!(2, report.cards.deck_size());
+        assert_eq!(report.cards.draw().unwrap().get_id(), "a".to_string());
+        assert_eq!(report.cards.draw().unwrap().get_id(), "e".to_string());
+
+        let lines: Vec<Option<u64>> = report
+            .rejected
+            .iter()
+            .map(|err| err.position().unwrap().line)
+            .collect();
+        assert_eq!(lines, vec![Some(3), Some(4), Some(5), Some(6)]);
+        assert!(matches!(report.rejected[0], LoadError::EmptyField { .. }));
+        assert!(matches!(report.rejected[1], LoadError::Malformed { .. }));
+        assert!(matches!(report.rejected[2], LoadError::DuplicateId { .. }));
+        assert!(matches!(report.rejected[3], LoadError::Encoding { .. }));
+    }
+
+    #[test]
+    fn test_csv_lenient_reader_without_errors() {
+        let data = "front,back\nfront_1,back_1\n";
+        let report = Csv::load_lenient::<Card>(data.as_bytes()).unwrap();
+
+        assert_eq!(1, report.cards.deck_size());
+        assert!(report.rejected.is_empty());
+    }
+
+    #[test]
+    fn test_csv_reader_errors() {
+        let err = load_csv("front,hint\nfront_1,hint_1\n").err().unwrap();
+        assert!(matches!(&err, LoadError::MissingColumn { column, .. } if column == "back"));
+        assert_eq!(err.position().unwrap().line, Some(2));
+
+        let err = load_csv("front,back\nfront_1,back_1\nfront_2, \n")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 3, record 2: `back` is empty");
+
+        let err = load_csv("id,front,back\na,front_1,back_1\na,front_2,back_2\n")
+            .err()
+            .unwrap();
+        assert!(matches!(&err, LoadError::DuplicateId { id, .. } if id == "a"));
+        assert_eq!(err.position().unwrap().record, Some(2));
+
+        let err = load_csv("front,back\nfront_1,back_1,extra\n")
+            .err()
+            .unwrap();
+        assert!(matches!(err, LoadError::Malformed { .. }));
+
+        let data = b"front,back\nfront_1,back_1\nfront_2,\xff\n";
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Csv::load(&data[..]);
+        let err = result.err().unwrap();
+        assert!(matches!(err, LoadError::Encoding { .. }));
+        assert_eq!(err.position().unwrap().line, Some(3));
+        assert_eq!(err.position().unwrap().column, Some(2));
+    }
+
+    #[test]
+    fn test_csv_reader_keeps_duplicate_content() {
+        let data = "front,back,hint\nhola,hello,\nhola,hello,\n";
+        let deck = load_csv(data).unwrap();
+
+        assert_eq!(2, deck.deck_size());
+        let findings = crate::Validator::default().validate(deck.as_ref());
+        assert!(matches!(
+            findings[0].problem,
+            crate::Problem::DuplicateCard { first_card: 1 }
+        ));
+    }
+
     #[test]
     fn test_csv_reader() {
         let data = "\
@@ -44,4 +496,314 @@ frint_3,back_3,,
         assert_eq!(card_2.get_front(), "front_2".to_string());
         assert_eq!(card_1.get_hint(), Some("hint_1".to_string()));
     }
+
+    #[test]
+    fn test_csv_reader_with_id() {
+        let data = "\
+id,front,back,hint
+card-1,front_1,back_1,hint_1
+,front_2,back_2,
+";
+        let mut result = Csv::load(data.as_bytes()).unwrap();
+
+        let card_1: Card = result.draw().unwrap();
+        let card_2: Card = result.draw().unwrap();
+
+        assert_eq!(card_1.get_id(), "card-1".to_string());
+        assert_eq!(
+            card_2.get_id(),
+            crate::card::content_id("front_2", "back_2")
+        );
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let cards: Cards<Card> = (0..2)
+            .map(|x| {
+                Card::new(
+                    format!("front_{}", x),
+                    format!("back_{}", x),
+                    format!("hint_{}", x),
+                )
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        Csv::save(&cards, &mut buffer).unwrap();
+
+        let data = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            data,
+            "\
+front,back,hint
+front_0,back_0,hint_0
+front_1,back_1,hint_1
+"
+        );
+    }
+
+    #[test]
+    fn test_csv_writer_empty_deck() {
+        let cards: Cards<Card> = Cards::new();
+
+        let mut buffer = Vec::new();
+        Csv::save(&cards, &mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), "front,back,hint\n");
+    }
+
+    #[test]
+    fn test_csv_writer_with_id() {
+        let mut cards: Cards<Card> = Cards::new();
+        let mut card = Card::new("front_0".to_string(), "back_0".to_string(), String::new());
+        card.set_id("card-0".to_string());
+        cards.add_card(card);
+        cards.add_card(Card::new(
+            "front_1".to_string(),
+            "back_1".to_string(),
+            String::new(),
+        ));
+
+        let mut buffer = Vec::new();
+        Csv::save(&cards, &mut buffer).unwrap();
+
+        let data = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            data,
+            "\
+front,back,hint,id
+front_0,back_0,,card-0
+front_1,back_1,,
+"
+        );
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let data = "\
+front,back,hint,
+front_1,back_1,hint_1,
+front_2,back_2,hint_2,
+frint_3,back_3,,
+";
+        let original: Box<dyn FlashCards<Card>> = Csv::load(data.as_bytes()).unwrap();
+
+        let mut buffer = Vec::new();
+        Csv::save(original.as_ref(), &mut buffer).unwrap();
+        let reloaded: Box<dyn FlashCards<Card>> = Csv::load(buffer.as_slice()).unwrap();
+
+        assert_eq!(original.deck_size(), reloaded.deck_size());
+        for (card_1, card_2) in original.iter().zip(reloaded.iter()) {
+            assert_eq!(card_1.get_front(), card_2.get_front());
+            assert_eq!(card_1.get_back(), card_2.get_back());
+            assert_eq!(card_1.get_hint(), card_2.get_hint());
+            assert_eq!(card_1.get_id(), card_2.get_id());
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trip_with_id() {
+        let data = "\
+id,front,back,hint
+card-1,front_1,back_1,hint_1
+";
+        let original: Box<dyn FlashCards<Card>> = Csv::load(data.as_bytes()).unwrap();
+
+        let mut buffer = Vec::new();
+        Csv::save(original.as_ref(), &mut buffer).unwrap();
+        let mut reloaded: Box<dyn FlashCards<Card>> = Csv::load(buffer.as_slice()).unwrap();
+
+        assert_eq!(reloaded.draw().unwrap().get_id(), "card-1".to_string());
+    }
+
+    #[test]
+    fn test_json_reader_flat_array() {
+        let data = r#"[
+            {"front": "front_1", "back": "back_1", "hint": "hint_1"},
+            {"front": "front_2", "back": "back_2", "id": "card-2"}
+        ]"#;
+        let mut result = Json::load(data.as_bytes()).unwrap();
+
+        let card_1: Card = result.draw().unwrap();
+        let card_2: Card = result.draw().unwrap();
+
+        assert_eq!(card_1.get_hint(), Some("hint_1".to_string()));
+        assert_eq!(card_2.get_hint(), None);
+        assert_eq!(card_2.get_id(), "card-2".to_string());
+    }
+
+    #[test]
+    fn test_json_reader_with_info() {
+        let data = r#"{
+            "name": "Capitals",
+            "description": "European capitals",
+            "cards": [{"front": "France", "back": "Paris"}]
+        }"#;
+        let (info, mut result) = Json::load_with_info::<Card>(data.as_bytes()).unwrap();
+
+        assert_eq!(info.name, Some("Capitals".to_string()));
+        assert_eq!(info.description, Some("European capitals".to_string()));
+        assert_eq!(result.draw().unwrap().get_back(), "Paris".to_string());
+    }
+
+    #[test]
+    fn test_json_reader_invalid() {
+        let data = r#"[{"front": "front_1", "back": "back_1"}, {"front": "front_2"}]"#;
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Json::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert_eq!(err.position(), Some(&Position::record(2)));
+        assert_eq!(err.to_string(), "record 2: missing required column `back`");
+
+        let data = "[\n  {\"front\": \"front_1\",\n}]";
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Json::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert!(matches!(err, LoadError::Malformed { .. }));
+        assert_eq!(err.position().unwrap().line, Some(3));
+
+        let data = r#"{"name": "No cards"}"#;
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Json::load(data.as_bytes());
+
+        assert!(matches!(result, Err(LoadError::Malformed { .. })));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let info = DeckInfo {
+            name: Some("Numbers".to_string()),
+            description: None,
+        };
+        let cards: Cards<Card> = (0..3)
+            .map(|x| {
+                Card::new(
+                    format!("front_{}", x),
+                    format!("back_{}", x),
+                    format!("hint_{}", x),
+                )
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        Json::save_with_info(&info, &cards, &mut buffer).unwrap();
+        let (reloaded_info, reloaded) = Json::load_with_info::<Card>(buffer.as_slice()).unwrap();
+
+        assert_eq!(info, reloaded_info);
+        assert_eq!(3, reloaded.deck_size());
+
+        let mut buffer = Vec::new();
+        Json::save(&cards, &mut buffer).unwrap();
+        let reloaded: Box<dyn FlashCards<Card>> = Json::load(buffer.as_slice()).unwrap();
+
+        for (card_1, card_2) in cards.iter().zip(reloaded.iter()) {
+            assert_eq!(card_1.get_front(), card_2.get_front());
+            assert_eq!(card_1.get_hint(), card_2.get_hint());
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_reader() {
+        let data = r#"
+name = "Capitals"
+
+[[cards]]
+front = "France"
+back = "Paris"
+hint = "Eiffel"
+
+[[cards]]
+id = "de"
+front = "Germany"
+back = """
+Berlin"""
+"#;
+        let mut result = Toml::load(data.as_bytes()).unwrap();
+
+        let card_1: Card = result.draw().unwrap();
+        let card_2: Card = result.draw().unwrap();
+
+        assert_eq!(card_1.get_hint(), Some("Eiffel".to_string()));
+        assert_eq!(card_2.get_back(), "Berlin".to_string());
+        assert_eq!(card_2.get_id(), "de".to_string());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_reader_malformed_entry() {
+        let data = r#"
+[[cards]]
+front = "France"
+back = "Paris"
+
+[[cards]]
+front = "Germany"
+"#;
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Toml::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert_eq!(err.to_string(), "record 2: missing required column `back`");
+
+        let data = "[[cards]]\nfront = \"France\"\nback = Paris\n";
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Toml::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert_eq!(err.position(), Some(&Position::line(3)));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_reader() {
+        let data = "\
+- front: France
+  back: Paris
+- front: Germany
+  back: |
+    Berlin
+    (since 1990)
+  hint: Brandenburg Gate
+";
+        let mut result = Yaml::load(data.as_bytes()).unwrap();
+
+        let card_1: Card = result.draw().unwrap();
+        let card_2: Card = result.draw().unwrap();
+
+        assert_eq!(card_1.get_hint(), None);
+        assert_eq!(card_2.get_back(), "Berlin\n(since 1990)\n".to_string());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_reader_with_info() {
+        let data = "\
+name: Capitals
+cards:
+  - front: France
+    back: Paris
+";
+        let result: Box<dyn FlashCards<Card>> = Yaml::load(data.as_bytes()).unwrap();
+
+        assert_eq!(1, result.deck_size());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_reader_malformed_entry() {
+        let data = "\
+- front: France
+  back: Paris
+- back: Berlin
+";
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Yaml::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert_eq!(err.to_string(), "record 2: missing required column `front`");
+
+        let data = "- front: France\n  back: [Paris\n";
+        let result: Result<Box<dyn FlashCards<Card>>, LoadError> = Yaml::load(data.as_bytes());
+        let err = result.err().unwrap();
+
+        assert!(matches!(err, LoadError::Malformed { .. }));
+        assert!(err.position().unwrap().line.is_some());
+    }
 }
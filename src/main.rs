@@ -0,0 +1,292 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufRead, Write};
+use std::rc::Weak;
+
+use flash_cards::seeded_rng;
+use flash_cards::{
+    Card, Cards, CardsManager, Csv, FlashCards, FlashCardsManager, FlipFlashCard, Loader, Quiz,
+};
+use rand::{thread_rng, RngCore};
+
+const HELP: &str = "\
+n: next card
+p: previous card
+f: flip card
+h: show hint
+a <answer>: type the answer to the current card
+s: shuffle remaining cards
+r: restart with every card
+q: quit";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Next,
+    Previous,
+    Flip,
+    Hint,
+    Answer(String),
+    Shuffle,
+    Restart,
+    Help,
+    Quit,
+}
+
+impl Command {
+    fn parse(input: &str) -> Option<Self> {
+        if let Some(answer) = input.trim().strip_prefix("a ") {
+            return Some(Command::Answer(answer.trim().to_string()));
+        }
+
+        match input.trim() {
+            "n" | "" => Some(Command::Next),
+            "p" => Some(Command::Previous),
+            "f" => Some(Command::Flip),
+            "h" => Some(Command::Hint),
+            "s" => Some(Command::Shuffle),
+            "r" => Some(Command::Restart),
+            "?" => Some(Command::Help),
+            "q" => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+fn show_card(
+    output: &mut impl Write,
+    manager: &impl FlashCardsManager<Card>,
+    card: Option<Weak<RefCell<Card>>>,
+) -> std::io::Result<()> {
+    let seen = manager.num_of_cards_seen();
+    let total = seen + manager.num_of_cards_in_deck();
+
+    match card.and_then(|card| card.upgrade()) {
+        Some(card) => {
+            let card = card.borrow();
+            writeln!(
+                output,
+                "[{}/{}] ({}) {}",
+                seen,
+                total,
+                card.get_state(),
+                card
+            )
+        }
+        None => writeln!(output, "[{}/{}] No card", seen, total),
+    }
+}
+
+fn run(
+    manager: &mut impl FlashCardsManager<Card>,
+    input: impl BufRead,
+    mut output: impl Write,
+    rng: &mut dyn RngCore,
+) -> std::io::Result<()> {
+    writeln!(output, "{}", HELP)?;
+    let card = manager.next_card();
+    show_card(&mut output, manager, card)?;
+
+    for line in input.lines() {
+        let card = match Command::parse(&line?) {
+            Some(Command::Next) => manager.next_card().or_else(|| {
+                // Stay on the last card once the deck runs out.
+                manager.current_card()
+            }),
+            Some(Command::Previous) => {
+                manager.previous_card();
+                manager.current_card()
+            }
+            Some(Command::Flip) => {
+                manager.flip_current_card();
+                manager.current_card()
+            }
+            Some(Command::Hint) => {
+                manager.try_to_flip_current_card_to_hint();
+                manager.current_card()
+            }
+            Some(Command::Answer(answer)) => {
+                match Quiz::default().answer_current_card(manager, &answer) {
+                    Some(answer) => writeln!(output, "{}: {}", answer.result, answer.expected)?,
+                    None => writeln!(output, "No card to answer")?,
+                }
+                manager.next_card().or_else(|| manager.current_card())
+            }
+            Some(Command::Shuffle) => {
+                manager.shuffle_with_rng(rng);
+                writeln!(output, "Shuffled the remaining cards")?;
+                manager.current_card()
+            }
+            Some(Command::Restart) => {
+                manager.add_previous_cards_to_deck();
+                manager.next_card()
+            }
+            Some(Command::Help) => {
+                writeln!(output, "{}", HELP)?;
+                continue;
+            }
+            Some(Command::Quit) => break,
+            None => {
+                writeln!(output, "Unknown command, type ? for help")?;
+                continue;
+            }
+        };
+
+        show_card(&mut output, manager, card)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn run_tui(manager: &mut CardsManager<Card>, rng: &mut dyn RngCore) {
+    if let Err(err) = flash_cards::tui::run(manager, rng) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_tui(_manager: &mut CardsManager<Card>, _rng: &mut dyn RngCore) {
+    eprintln!("flash_cards was built without the `tui` feature");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let tui = args.iter().any(|arg| arg == "--tui");
+    args.retain(|arg| arg != "--tui");
+
+    // A shared seed gives everyone the same shuffled order.
+    let seeded = args.iter().any(|arg| arg == "--seed");
+    let mut rng: Box<dyn RngCore> = match args.iter().position(|arg| arg == "--seed") {
+        Some(index) => {
+            let seed = args.get(index + 1).and_then(|seed| seed.parse().ok());
+            match seed {
+                Some(seed) => {
+                    args.drain(index..=index + 1);
+                    Box::new(seeded_rng(seed))
+                }
+                None => {
+                    eprintln!("--seed needs a number");
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => Box::new(thread_rng()),
+    };
+
+    let path = match args.first() {
+        Some(path) => path.clone(),
+        None => {
+            eprintln!("usage: flash_cards [--tui] [--seed <n>] <deck.csv>");
+            std::process::exit(2);
+        }
+    };
+
+    let deck = match File::open(&path) {
+        Ok(file) => Csv::load(file),
+        Err(err) => Err(err.into()),
+    };
+    let deck = match deck {
+        Ok(deck) => deck,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut cards: Cards<Card> = Cards::new();
+    cards.add_deck(deck);
+    let mut manager = CardsManager::create_from_deck(cards);
+    if seeded {
+        manager.shuffle_with_rng(&mut rng);
+    }
+
+    if tui {
+        run_tui(&mut manager, &mut rng);
+        return;
+    }
+
+    let stdin = std::io::stdin();
+    if let Err(err) = run(&mut manager, stdin.lock(), std::io::stdout(), &mut rng) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+
+    manager.finish_session();
+    let report = manager.stats().report(3);
+    println!(
+        "Studied {} cards in {}s",
+        report.cards_studied,
+        report.time_spent_ms / 1000
+    );
+    if let Some(accuracy) = report.accuracy {
+        println!("Accuracy: {:.0}%", accuracy * 100.0);
+    }
+    for card in &report.cards_needing_hints {
+        println!("Needed a hint: {}", card.front);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_manager(size: usize) -> CardsManager<Card> {
+        let cards: Cards<Card> = (0..size)
+            .map(|x| {
+                Card::new(
+                    format!("{} - front", x),
+                    format!("{} - back", x),
+                    format!("{} - hint", x),
+                )
+            })
+            .collect();
+
+        CardsManager::create_from_deck(cards)
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Command::parse("n"), Some(Command::Next));
+        assert_eq!(Command::parse(""), Some(Command::Next));
+        assert_eq!(Command::parse(" f \n"), Some(Command::Flip));
+        assert_eq!(Command::parse("q"), Some(Command::Quit));
+        assert_eq!(
+            Command::parse("a  Paris \n"),
+            Some(Command::Answer("Paris".to_string()))
+        );
+        assert_eq!(Command::parse("x"), None);
+    }
+
+    #[test]
+    fn test_run_session() {
+        let mut manager = create_test_manager(2);
+        let input = "f\nh\nn\nn\np\nx\nr\nq\nn\n";
+        let mut output = Vec::new();
+
+        run(
+            &mut manager,
+            input.as_bytes(),
+            &mut output,
+            &mut seeded_rng(1),
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().skip(HELP.lines().count()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "[1/2] (front) 0 - front",
+                "[1/2] (back) 0 - back",
+                "[1/2] (hint) 0 - hint",
+                "[2/2] (front) 1 - front",
+                "[2/2] (front) 1 - front",
+                "[1/2] (hint) 0 - hint",
+                "Unknown command, type ? for help",
+                "[1/2] (hint) 0 - hint",
+            ]
+        );
+    }
+}
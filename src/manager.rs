@@ -1,47 +1,691 @@
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::rc::{Rc, Weak};
+use std::time::{Instant, SystemTime};
 
-use rand::{thread_rng, Rng};
+use rand::seq::SliceRandom;
+use rand::RngCore;
 
-use crate::enums::FlashCardState;
-use crate::traits::{FlashCard, FlashCards, FlashCardsManager, FlipFlashCard};
+use crate::enums::{FlashCardState, Grade, StudyDirection};
+use crate::errors::SnapshotError;
+use crate::history::{Review, ReviewLog};
+use crate::scheduler::Unscheduled;
+use crate::snapshot::{CardSnapshot, Snapshot};
+use crate::stats::SessionStats;
+use crate::traits::{
+    CardCell, CardRef, FlashCard, FlashCards, FlashCardsManager, FlipFlashCard, Scheduler,
+};
 
-pub struct CardsManager<T>
+impl<T> CardRef<T> for Weak<RefCell<T>> {
+    fn read<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let card = self.upgrade()?;
+        let card = card.try_borrow().ok()?;
+        Some(f(&card))
+    }
+}
+
+impl<T: FlipFlashCard> CardCell for Rc<RefCell<T>> {
+    type Card = T;
+    type Weak = Weak<RefCell<T>>;
+    type Ref<'a>
+        = Ref<'a, T>
+    where
+        T: 'a;
+    type RefMut<'a>
+        = RefMut<'a, T>
+    where
+        T: 'a;
+
+    fn new(card: T) -> Self {
+        Rc::new(RefCell::new(card))
+    }
+
+    fn downgrade(&self) -> Self::Weak {
+        Rc::downgrade(self)
+    }
+
+    fn get_card(&self) -> Option<Self::Ref<'_>> {
+        RefCell::try_borrow(self).ok()
+    }
+
+    fn get_card_mut(&self) -> Option<Self::RefMut<'_>> {
+        RefCell::try_borrow_mut(self).ok()
+    }
+
+    fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(self) as *const ()
+    }
+}
+
+// Names one entry in a CardsManager. Handles stay valid for the life of the
+// manager, wherever the card moves to, and an entry set aside by a change of
+// study direction keeps its handle.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct CardHandle(usize);
+
+struct ManagedCard<C, S: Scheduler> {
+    handle: CardHandle,
+    card: C,
+    // Read once when the card is added, so that a card borrowed by the
+    // caller can still be graded, logged and saved.
+    card_id: String,
+    schedule: S::State,
+    last_grade: Option<Grade>,
+    requeued: bool,
+    shown_at: Option<Instant>,
+    hint_shown: bool,
+    // Reversed entries show the back first and are answered with the front.
+    reversed: bool,
+    // The side this entry shows. Both directions of a card share the card,
+    // so the side is put back on the card whenever the entry is shown.
+    state: FlashCardState,
+    // Moved to the front on request, so it is shown next even if not due.
+    pulled: bool,
+    // Sent back by previous_card, so it is shown again on the way forward
+    // even if not due.
+    sent_back: bool,
+}
+
+impl<C, S> ManagedCard<C, S>
 where
-    T: for<'de> FlashCard<'de>,
+    C: CardCell,
+    S: Scheduler,
 {
-    unseen_cards: VecDeque<Rc<RefCell<T>>>,
-    seen_cards: VecDeque<Rc<RefCell<T>>>,
+    fn new(card: C::Card, handle: CardHandle) -> Self {
+        ManagedCard {
+            handle,
+            card_id: card.get_id(),
+            card: C::new(card),
+            schedule: S::State::default(),
+            last_grade: None,
+            requeued: false,
+            shown_at: None,
+            hint_shown: false,
+            reversed: false,
+            state: FlashCardState::Front,
+            pulled: false,
+            sent_back: false,
+        }
+    }
+
+    // The same card studied in the other direction.
+    fn new_counterpart(&self, handle: CardHandle) -> Self {
+        ManagedCard {
+            handle,
+            card: self.card.clone(),
+            card_id: self.card_id.clone(),
+            schedule: S::State::default(),
+            last_grade: None,
+            requeued: false,
+            shown_at: None,
+            hint_shown: false,
+            reversed: !self.reversed,
+            state: match self.reversed {
+                true => FlashCardState::Front,
+                false => FlashCardState::Back,
+            },
+            pulled: false,
+            sent_back: false,
+        }
+    }
+
+    fn start_state(&self) -> FlashCardState {
+        match self.reversed {
+            true => FlashCardState::Back,
+            false => FlashCardState::Front,
+        }
+    }
+
+    // Both directions of a card are reviewed and scheduled separately, so
+    // the reversed one is logged under its own id.
+    fn snapshot(&self) -> CardSnapshot {
+        CardSnapshot {
+            card_id: self.card_id.clone(),
+            reversed: self.reversed,
+            state: self.state,
+            last_grade: self.last_grade,
+            requeued: self.requeued,
+            hint_shown: self.hint_shown,
+            pulled: self.pulled,
+            sent_back: self.sent_back,
+        }
+    }
+
+    fn review_id(&self) -> String {
+        match self.reversed {
+            true => format!("{}:reverse", self.card_id),
+            false => self.card_id.clone(),
+        }
+    }
+
+    fn replay(&mut self, scheduler: &S, log: &ReviewLog) {
+        for review in log.reviews_for(&self.review_id()) {
+            self.schedule = scheduler.review(&self.schedule, review.grade, review.reviewed_at());
+            self.last_grade = Some(review.grade);
+        }
+    }
+
+    // While the entry is on screen the card can also be flipped through its
+    // Weak reference, so the card's own side is the one that counts.
+    fn current_state(&self) -> FlashCardState {
+        self.card
+            .get_card()
+            .map(|card| *card.get_state())
+            .unwrap_or(self.state)
+    }
+}
+
+// Single-threaded manager, cards are shared as Weak<RefCell<T>>.
+pub type CardsManager<T, S = Unscheduled> = GenericCardsManager<Rc<RefCell<T>>, S>;
+
+// The queues and scheduling behind CardsManager and SyncCardsManager, over
+// whichever cell the cards are kept in.
+pub struct GenericCardsManager<C, S = Unscheduled>
+where
+    C: CardCell,
+    S: Scheduler,
+{
+    unseen_cards: VecDeque<ManagedCard<C, S>>,
+    seen_cards: VecDeque<ManagedCard<C, S>>,
+    // Entries for a direction that is not being studied, kept with their
+    // handles and schedules in case the direction is switched back.
+    set_aside: Vec<ManagedCard<C, S>>,
+    scheduler: S,
+    requeue_failed_cards: bool,
+    review_log: ReviewLog,
+    direction: StudyDirection,
+    stats: SessionStats,
+    // Number of handles given out, the next handle is this one.
+    handle_count: usize,
+}
+
+impl<C> GenericCardsManager<C>
+where
+    C: CardCell,
+{
+    pub fn create_from_deck(deck: impl FlashCards<C::Card>) -> Self {
+        Self::create_scheduled_from_deck(deck, Unscheduled {})
+    }
 }
 
-impl<T> CardsManager<T>
+impl<C, S> GenericCardsManager<C, S>
 where
-    T: for<'de> FlashCard<'de> + FlipFlashCard,
+    C: CardCell,
+    S: Scheduler,
 {
-    pub fn create_from_deck(mut deck: impl FlashCards<T>) -> Self {
+    pub fn create_scheduled_from_deck(mut deck: impl FlashCards<C::Card>, scheduler: S) -> Self {
         let mut unseen_cards = VecDeque::new();
         let mut card = deck.draw();
         while card.is_some() {
-            unseen_cards.push_back(Rc::new(RefCell::new(card.unwrap())));
+            let handle = CardHandle(unseen_cards.len());
+            unseen_cards.push_back(ManagedCard::new(card.unwrap(), handle));
             card = deck.draw();
         }
+        let handle_count = unseen_cards.len();
         Self {
             seen_cards: VecDeque::new(),
             unseen_cards,
+            set_aside: Vec::new(),
+            scheduler,
+            requeue_failed_cards: true,
+            review_log: ReviewLog::new(),
+            direction: StudyDirection::Forward,
+            stats: SessionStats::new(),
+            handle_count,
+        }
+    }
+
+    pub fn set_requeue_failed_cards(&mut self, requeue: bool) {
+        self.requeue_failed_cards = requeue;
+    }
+
+    pub fn study_direction(&self) -> StudyDirection {
+        self.direction
+    }
+
+    // Meant to be called before studying starts. An entry whose direction is
+    // no longer studied is set aside and the card's other entry takes its
+    // place. With StudyDirection::Both the entries a card is missing are
+    // queued after every unseen card.
+    pub fn set_study_direction(&mut self, direction: StudyDirection) {
+        let studied = |reversed: bool| match direction {
+            StudyDirection::Forward => !reversed,
+            StudyDirection::Reverse => reversed,
+            StudyDirection::Both => true,
+        };
+        let mut queued: HashSet<(*const (), bool)> = self
+            .seen_cards
+            .iter()
+            .chain(self.unseen_cards.iter())
+            .filter(|managed| studied(managed.reversed))
+            .map(|managed| (managed.card.as_ptr(), managed.reversed))
+            .collect();
+
+        let first_new_handle = self.handle_count;
+        let mut set_aside = std::mem::take(&mut self.set_aside);
+        for queue in [&mut self.seen_cards, &mut self.unseen_cards] {
+            for managed in std::mem::take(queue) {
+                if studied(managed.reversed) {
+                    queue.push_back(managed);
+                    continue;
+                }
+                if queued.insert((managed.card.as_ptr(), !managed.reversed)) {
+                    let counterpart =
+                        Self::counterpart(&managed, &mut set_aside, &mut self.handle_count);
+                    queue.push_back(counterpart);
+                }
+                set_aside.push(managed);
+            }
+        }
+
+        if direction == StudyDirection::Both {
+            let missing: Vec<ManagedCard<C, S>> = self
+                .seen_cards
+                .iter()
+                .chain(self.unseen_cards.iter())
+                .filter(|managed| !queued.contains(&(managed.card.as_ptr(), !managed.reversed)))
+                .map(|managed| Self::counterpart(managed, &mut set_aside, &mut self.handle_count))
+                .collect();
+            self.unseen_cards.extend(missing);
         }
+
+        for managed in self
+            .seen_cards
+            .iter_mut()
+            .chain(self.unseen_cards.iter_mut())
+        {
+            managed.state = managed.start_state();
+            // A replayed log can already hold reviews for a direction that is
+            // only now being studied.
+            if managed.handle.0 >= first_new_handle {
+                managed.replay(&self.scheduler, &self.review_log);
+            }
+        }
+        self.set_aside = set_aside;
+        self.direction = direction;
+        self.track_current_card();
+    }
+
+    // The card's entry for the other direction, taken from those set aside or
+    // made with a new handle the first time it is needed.
+    fn counterpart(
+        managed: &ManagedCard<C, S>,
+        set_aside: &mut Vec<ManagedCard<C, S>>,
+        handle_count: &mut usize,
+    ) -> ManagedCard<C, S> {
+        let found = set_aside.iter().position(|other| {
+            other.card.as_ptr() == managed.card.as_ptr() && other.reversed != managed.reversed
+        });
+        match found {
+            Some(index) => set_aside.swap_remove(index),
+            None => {
+                *handle_count += 1;
+                managed.new_counterpart(CardHandle(*handle_count - 1))
+            }
+        }
+    }
+
+    fn find(&self, handle: CardHandle) -> Option<&ManagedCard<C, S>> {
+        self.seen_cards
+            .iter()
+            .chain(self.unseen_cards.iter())
+            .chain(self.set_aside.iter())
+            .find(|managed| managed.handle == handle)
+    }
+
+    pub fn handles(&self) -> impl Iterator<Item = CardHandle> + '_ {
+        self.seen_cards
+            .iter()
+            .chain(self.unseen_cards.iter())
+            .map(|managed| managed.handle)
+    }
+
+    pub fn current_handle(&self) -> Option<CardHandle> {
+        self.seen_cards.front().map(|managed| managed.handle)
+    }
+
+    pub fn next_handle(&mut self) -> Option<CardHandle> {
+        self.next_card()?;
+        self.current_handle()
+    }
+
+    pub fn previous_handle(&mut self) -> Option<CardHandle> {
+        self.previous_card()?;
+        self.current_handle()
+    }
+
+    // Returns None rather than panicking when the handle is unknown or the
+    // card is already borrowed through its Weak reference.
+    pub fn get(&self, handle: CardHandle) -> Option<C::Ref<'_>> {
+        self.find(handle)?.card.get_card()
+    }
+
+    pub fn get_mut(&mut self, handle: CardHandle) -> Option<C::RefMut<'_>> {
+        self.find(handle)?.card.get_card_mut()
+    }
+
+    pub fn is_reversed(&self, handle: CardHandle) -> Option<bool> {
+        self.find(handle).map(|managed| managed.reversed)
+    }
+
+    pub fn grade(&self, handle: CardHandle) -> Option<Grade> {
+        self.find(handle)?.last_grade
+    }
+
+    // Cards in the order of the session: the seen cards from the first one
+    // shown, followed by the unseen cards in the order they will be shown.
+    fn in_session_order(&self) -> impl Iterator<Item = &ManagedCard<C, S>> {
+        self.seen_cards.iter().rev().chain(self.unseen_cards.iter())
+    }
+
+    pub fn handle_at(&self, position: usize) -> Option<CardHandle> {
+        self.in_session_order()
+            .nth(position)
+            .map(|managed| managed.handle)
+    }
+
+    pub fn position_of(&self, handle: CardHandle) -> Option<usize> {
+        self.in_session_order()
+            .position(|managed| managed.handle == handle)
+    }
+
+    pub fn find_by_id(&self, card_id: &str) -> Option<CardHandle> {
+        self.in_session_order()
+            .find(|managed| managed.card_id == card_id)
+            .map(|managed| managed.handle)
+    }
+
+    // Case-insensitive search of the front, back and hint of every card.
+    pub fn search(&self, query: &str) -> Vec<CardHandle> {
+        let query = query.to_lowercase();
+        self.in_session_order()
+            .filter(|managed| {
+                let card = match managed.card.get_card() {
+                    Some(card) => card,
+                    None => return false,
+                };
+                [
+                    Some(card.get_front()),
+                    Some(card.get_back()),
+                    card.get_hint(),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|text| text.to_lowercase().contains(&query))
+            })
+            .map(|managed| managed.handle)
+            .collect()
+    }
+
+    // Puts the card at the front of the unseen cards so that it is the next
+    // card shown, even when a scheduler does not consider it due.
+    pub fn move_to_front(&mut self, handle: CardHandle) -> bool {
+        let position = |queue: &VecDeque<ManagedCard<C, S>>| {
+            queue.iter().position(|managed| managed.handle == handle)
+        };
+        self.leave_current_card();
+        let card = match position(&self.seen_cards) {
+            Some(index) => self.seen_cards.remove(index),
+            None => position(&self.unseen_cards).and_then(|index| self.unseen_cards.remove(index)),
+        };
+
+        match card {
+            Some(mut card) => {
+                self.unpull_front_card();
+                card.pulled = true;
+                self.unseen_cards.push_front(card);
+                self.track_current_card();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn jump_to(&mut self, handle: CardHandle) -> Option<C::Weak> {
+        if !self.move_to_front(handle) {
+            return None;
+        }
+        self.next_card()
+    }
+
+    pub fn jump_to_position(&mut self, position: usize) -> Option<C::Weak> {
+        self.jump_to(self.handle_at(position)?)
+    }
+
+    pub fn jump_to_id(&mut self, card_id: &str) -> Option<C::Weak> {
+        self.jump_to(self.find_by_id(card_id)?)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            direction: self.direction,
+            seen: self
+                .seen_cards
+                .iter()
+                .enumerate()
+                .map(|(index, managed)| {
+                    let mut card = managed.snapshot();
+                    if index == 0 {
+                        card.state = managed.current_state();
+                    }
+                    card
+                })
+                .collect(),
+            unseen: self
+                .unseen_cards
+                .iter()
+                .map(|managed| managed.snapshot())
+                .collect(),
+        }
+    }
+
+    // Puts the cards back in the order and state they had when the snapshot
+    // was taken. The deck must hold exactly the cards in the snapshot,
+    // otherwise nothing is changed, not even the study direction.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        let directions: &[bool] = match snapshot.direction {
+            StudyDirection::Forward => &[false],
+            StudyDirection::Reverse => &[true],
+            StudyDirection::Both => &[false, true],
+        };
+        // The entries the deck has once the snapshot's direction is set.
+        let mut cards_found = HashSet::new();
+        let mut expected: HashMap<(String, bool), usize> = HashMap::new();
+        for managed in self.seen_cards.iter().chain(self.unseen_cards.iter()) {
+            if cards_found.insert(managed.card.as_ptr()) {
+                for reversed in directions {
+                    *expected
+                        .entry((managed.card_id.clone(), *reversed))
+                        .or_default() += 1;
+                }
+            }
+        }
+        for card in snapshot.seen.iter().chain(snapshot.unseen.iter()) {
+            match expected.get_mut(&(card.card_id.clone(), card.reversed)) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => {
+                    return Err(SnapshotError::UnknownCard {
+                        id: card.card_id.clone(),
+                    })
+                }
+            }
+        }
+        if let Some(((id, _), _)) = expected.iter().find(|(_, count)| **count > 0) {
+            return Err(SnapshotError::MissingCard { id: id.clone() });
+        }
+
+        if self.direction != snapshot.direction {
+            self.set_study_direction(snapshot.direction);
+        }
+
+        let mut entries: Vec<Option<ManagedCard<C, S>>> = self
+            .seen_cards
+            .drain(..)
+            .chain(self.unseen_cards.drain(..))
+            .map(Some)
+            .collect();
+
+        // Indexes are stored back to front so that popping hands out cards
+        // with the same id in deck order.
+        let mut by_id: HashMap<(String, bool), Vec<usize>> = HashMap::new();
+        for (index, managed) in entries.iter().enumerate().rev() {
+            let managed = managed.as_ref().unwrap();
+            let key = (managed.card_id.clone(), managed.reversed);
+            by_id.entry(key).or_default().push(index);
+        }
+
+        let cards = snapshot.seen.iter().chain(snapshot.unseen.iter());
+        for (position, card) in cards.enumerate() {
+            let key = (card.card_id.clone(), card.reversed);
+            // Every card was checked above, so there is an entry for it.
+            let index = by_id.get_mut(&key).and_then(|indexes| indexes.pop());
+            let mut managed = entries[index.unwrap()].take().unwrap();
+            managed.state = card.state;
+            managed.last_grade = card.last_grade;
+            managed.requeued = card.requeued;
+            managed.hint_shown = card.hint_shown;
+            managed.pulled = card.pulled;
+            managed.sent_back = card.sent_back;
+
+            if position < snapshot.seen.len() {
+                self.seen_cards.push_back(managed);
+            } else {
+                self.unseen_cards.push_back(managed);
+            }
+        }
+
+        self.track_current_card();
+
+        Ok(())
+    }
+
+    // A pulled card only jumps the queue while it is the next card, once
+    // another card is put before it, it waits until it is due like the rest.
+    fn unpull_front_card(&mut self) {
+        if let Some(managed) = self.unseen_cards.front_mut() {
+            managed.pulled = false;
+        }
+    }
+
+    fn leave_current_card(&mut self) {
+        if let Some(managed) = self.seen_cards.front_mut() {
+            managed.state = managed.current_state();
+        }
+    }
+
+    // Called whenever the current card may have changed. It puts the side of
+    // the new current entry on the card and moves the stats clock over.
+    fn track_current_card(&mut self) {
+        if let Some(managed) = self.seen_cards.front() {
+            if let Some(mut card) = managed.card.get_card_mut() {
+                card.set_state(managed.state);
+            }
+        }
+
+        let card = self.seen_cards.front().map(|managed| {
+            // A borrowed card's front is filled in once it can be read.
+            let front = managed
+                .card
+                .get_card()
+                .map(|card| card.get_front())
+                .unwrap_or_default();
+            (managed.review_id(), front)
+        });
+        self.stats.show(
+            card.as_ref()
+                .map(|(card_id, front)| (card_id.as_str(), front.as_str())),
+            Instant::now(),
+        );
+    }
+
+    // Every card in the session, seen or not, in no particular order.
+    pub fn cards(&self) -> impl Iterator<Item = C::Weak> + '_ {
+        self.seen_cards
+            .iter()
+            .chain(self.unseen_cards.iter())
+            .map(|managed| managed.card.downgrade())
+    }
+
+    pub fn review_log(&self) -> &ReviewLog {
+        &self.review_log
+    }
+
+    pub fn review_log_mut(&mut self) -> &mut ReviewLog {
+        &mut self.review_log
+    }
+
+    // Rebuilds every card's schedule from a log saved by an earlier session.
+    // New reviews are recorded on top of it.
+    pub fn replay_review_log(&mut self, log: ReviewLog) {
+        for managed in self
+            .unseen_cards
+            .iter_mut()
+            .chain(self.seen_cards.iter_mut())
+            .chain(self.set_aside.iter_mut())
+        {
+            managed.replay(&self.scheduler, &log);
+        }
+
+        self.review_log = log;
+    }
+
+    // Index of the unseen card that has been due the longest. Cards that have
+    // never been reviewed count as the most overdue of all, and cards failed
+    // earlier in the session stay available even though they are not due yet.
+    // A card moved to the front or sent back by previous_card comes first.
+    fn most_overdue_card(&self, now: SystemTime) -> Option<usize> {
+        if self
+            .unseen_cards
+            .front()
+            .is_some_and(|managed| managed.pulled || managed.sent_back)
+        {
+            return Some(0);
+        }
+
+        self.unseen_cards
+            .iter()
+            .enumerate()
+            .filter(|(_, managed)| {
+                managed.requeued
+                    || self
+                        .scheduler
+                        .due(&managed.schedule)
+                        .is_none_or(|due| due <= now)
+            })
+            .min_by_key(|(_, managed)| {
+                self.scheduler
+                    .due(&managed.schedule)
+                    .unwrap_or(SystemTime::UNIX_EPOCH)
+            })
+            .map(|(index, _)| index)
     }
 }
 
-impl<T> FlashCardsManager<T> for CardsManager<T>
+impl<C, S> FlashCardsManager<C::Card, C::Weak> for GenericCardsManager<C, S>
 where
-    T: for<'de> FlashCard<'de> + FlipFlashCard,
+    C: CardCell,
+    S: Scheduler,
 {
-    fn next_card(&mut self) -> Option<Weak<RefCell<T>>> {
-        match self.unseen_cards.pop_front() {
-            Some(card) => {
-                let card_weak_ref = Rc::downgrade(&card);
+    fn next_card(&mut self) -> Option<C::Weak> {
+        let index = self.most_overdue_card(SystemTime::now())?;
+        self.leave_current_card();
+
+        match self.unseen_cards.remove(index) {
+            Some(mut card) => {
+                card.shown_at = Some(Instant::now());
+                card.hint_shown = false;
+                card.pulled = false;
+                card.sent_back = false;
+                // The side is only reset when reverse study is in use, as
+                // forward study has always kept it.
+                if self.direction != StudyDirection::Forward {
+                    card.state = card.start_state();
+                }
+
+                let card_weak_ref = card.card.downgrade();
                 self.seen_cards.push_front(card);
+                self.track_current_card();
+                self.stats.record_view();
 
                 Some(card_weak_ref)
             }
@@ -49,11 +693,17 @@ where
         }
     }
 
-    fn previous_card(&mut self) -> Option<Weak<RefCell<T>>> {
+    fn previous_card(&mut self) -> Option<C::Weak> {
+        self.leave_current_card();
+        // Counted on the card being left, before the clock moves off it.
+        self.stats.record_back_navigation();
         match self.seen_cards.pop_front() {
-            Some(card) => {
-                let card_weak_ref = Rc::downgrade(&card);
+            Some(mut card) => {
+                let card_weak_ref = card.card.downgrade();
+                self.unpull_front_card();
+                card.sent_back = true;
                 self.unseen_cards.push_front(card);
+                self.track_current_card();
 
                 Some(card_weak_ref)
             }
@@ -61,23 +711,18 @@ where
         }
     }
 
-    fn shuffle(&mut self) {
-        let mut rng = thread_rng();
-        let total_num_of_cards = self.unseen_cards.len();
-
-        for _ in 0..total_num_of_cards {
-            let rand_num_1 = rng.gen_range(0..total_num_of_cards);
-            let rand_num_2 = rng.gen_range(0..total_num_of_cards);
-
-            self.unseen_cards.swap(rand_num_1, rand_num_2);
-        }
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore) {
+        self.unseen_cards.make_contiguous().shuffle(rng);
     }
 
     fn add_previous_cards_to_deck(&mut self) {
+        self.leave_current_card();
+        self.unpull_front_card();
         for _ in 0..self.num_of_cards_seen() {
             self.unseen_cards
                 .push_front(self.seen_cards.pop_front().unwrap());
         }
+        self.track_current_card();
     }
 
     fn num_of_cards_seen(&self) -> usize {
@@ -88,11 +733,11 @@ where
         self.unseen_cards.len()
     }
 
-    fn current_card(&mut self) -> Option<Weak<RefCell<T>>> {
+    fn current_card(&mut self) -> Option<C::Weak> {
         match self.seen_cards.pop_front() {
             None => None,
             Some(card) => {
-                let card_weak_ref = Rc::downgrade(&card);
+                let card_weak_ref = card.card.downgrade();
                 self.seen_cards.push_front(card);
 
                 Some(card_weak_ref)
@@ -103,12 +748,21 @@ where
     fn flip_current_card(&mut self) {
         match self.seen_cards.pop_front() {
             None => {}
-            Some(card) => {
-                let mut card_instance = card.borrow_mut();
-                card_instance.flip();
-                drop(card_instance);
+            Some(mut card) => {
+                // A card borrowed by the caller is left as it is instead of
+                // panicking.
+                let flipped = match card.card.get_card_mut() {
+                    Some(mut card_instance) => {
+                        card.state = *card_instance.flip();
+                        true
+                    }
+                    None => false,
+                };
 
                 self.seen_cards.push_front(card);
+                if flipped {
+                    self.stats.record_flip();
+                }
             }
         }
     }
@@ -116,14 +770,23 @@ where
     fn try_to_flip_current_card_to_hint(&mut self) {
         match self.seen_cards.pop_front() {
             None => {}
-            Some(card) => {
-                let mut card_instance = card.borrow_mut();
-                if card_instance.get_hint().is_some() {
-                    card_instance.set_state(FlashCardState::Hint)
-                }
-                drop(card_instance);
+            Some(mut card) => {
+                let (has_hint, shown) = match card.card.get_card_mut() {
+                    Some(mut card_instance) if card_instance.get_hint().is_some() => {
+                        let shown = *card_instance.get_state() != FlashCardState::Hint;
+                        card_instance.set_state(FlashCardState::Hint);
+                        card.state = FlashCardState::Hint;
+                        (true, shown)
+                    }
+                    _ => (false, false),
+                };
+                card.hint_shown |= has_hint;
 
                 self.seen_cards.push_front(card);
+                // Asking again while the hint is on screen is not counted.
+                if shown {
+                    self.stats.record_hint();
+                }
             }
         }
     }
@@ -131,21 +794,84 @@ where
     fn reset_current_card_state(&mut self) {
         match self.seen_cards.pop_front() {
             None => {}
-            Some(card) => {
-                let mut card_instance = card.borrow_mut();
-                card_instance.set_state(FlashCardState::Front);
-                drop(card_instance);
+            Some(mut card) => {
+                if let Some(mut card_instance) = card.card.get_card_mut() {
+                    card.state = card.start_state();
+                    card_instance.set_state(card.state);
+                }
 
                 self.seen_cards.push_front(card);
             }
         }
     }
+
+    fn grade_current_card(&mut self, grade: Grade) {
+        self.stats.record_grade(grade);
+        let mut card = match self.seen_cards.pop_front() {
+            None => return,
+            Some(card) => card,
+        };
+
+        let now = SystemTime::now();
+        let card_id = card.review_id();
+        card.state = card.current_state();
+        let state = match card.hint_shown {
+            true => FlashCardState::Hint,
+            false => card.state,
+        };
+        let time_taken = card
+            .shown_at
+            .map(|shown_at| shown_at.elapsed())
+            .unwrap_or_default();
+        self.review_log
+            .record(Review::new(card_id, now, grade, time_taken, state));
+
+        card.schedule = self.scheduler.review(&card.schedule, grade, now);
+        card.last_grade = Some(grade);
+        card.requeued = self.requeue_failed_cards && grade == Grade::Again;
+
+        if card.requeued {
+            self.unseen_cards.push_back(card);
+        } else {
+            self.seen_cards.push_front(card);
+        }
+        self.track_current_card();
+    }
+
+    fn current_card_grade(&self) -> Option<Grade> {
+        self.seen_cards.front().and_then(|card| card.last_grade)
+    }
+
+    fn is_current_card_reversed(&self) -> bool {
+        self.seen_cards.front().is_some_and(|card| card.reversed)
+    }
+
+    fn stats(&self) -> &SessionStats {
+        &self.stats
+    }
+
+    fn finish_session(&mut self) {
+        self.stats.show(None, Instant::now());
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::{Card, Cards};
+    use crate::{Card, Cards, Fsrs, Sm2};
+    use std::time::Duration;
+
+    pub(crate) fn create_test_cards(size: usize) -> Cards<Card> {
+        (0..size)
+            .map(|x| {
+                Card::new(
+                    format!("{} - front", x),
+                    format!("{} - back", x),
+                    format!("{} - hint", x),
+                )
+            })
+            .collect()
+    }
 
     fn create_test_manager() -> CardsManager<Card> {
         let cards: Cards<Card> = (0..10)
@@ -161,7 +887,26 @@ mod tests {
         CardsManager::create_from_deck(cards)
     }
 
+    pub(crate) fn create_sized_test_manager(size: usize) -> CardsManager<Card> {
+        CardsManager::create_from_deck(create_test_cards(size))
+    }
+
     #[test]
+    fn test_cards() {
+        let mut card_manager = create_test_manager();
+        card_manager.next_card();
+
+        let fronts: Vec<String> = card_manager
+            .cards()
+            .map(|card| card.upgrade().unwrap().borrow().get_front())
+            .collect();
+
+        assert_eq!(10, fronts.len());
+        assert_eq!("0 - front".to_string(), fronts[0]);
+    }
+
+    #[test]
+    #[allow(clippy::let_unit_value)]
     fn test_reset_card_to_front() {
         let mut card_manager = create_test_manager();
 
@@ -292,4 +1037,724 @@ mod tests {
 
         assert!(is_shuffled);
     }
+
+    #[test]
+    fn test_shuffle_with_seeded_rng() {
+        let fronts = |seed: u64| {
+            let mut card_manager = create_test_manager();
+            card_manager.shuffle_with_rng(&mut crate::seeded_rng(seed));
+
+            (0..10)
+                .map(|_| {
+                    let card = card_manager.next_card().unwrap().upgrade().unwrap();
+                    let front = card.borrow().get_front();
+                    front
+                })
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(fronts(42), fronts(42));
+        assert_ne!(fronts(42), fronts(43));
+    }
+
+    #[test]
+    fn test_scheduled_next_card_skips_cards_not_due() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+
+        let first_card = card_manager.next_card().unwrap();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.add_previous_cards_to_deck();
+
+        let second_card = card_manager.next_card().unwrap();
+        assert!(!first_card.ptr_eq(&second_card));
+        card_manager.grade_current_card(Grade::Good);
+
+        assert!(card_manager.next_card().is_none());
+        assert_eq!(1, card_manager.num_of_cards_in_deck());
+    }
+
+    #[test]
+    fn test_scheduled_previous_card_is_shown_again() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(1), Sm2::new());
+        card_manager.next_card();
+        card_manager.grade_current_card(Grade::Good);
+        assert!(card_manager.next_card().is_none());
+
+        assert!(card_manager.previous_card().is_some());
+        let card = card_manager.next_card();
+        assert_eq!("0 - front".to_string(), front_and_state(card).0);
+        assert!(card_manager.next_card().is_none());
+
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+        for _ in 0..2 {
+            card_manager.next_card();
+            card_manager.grade_current_card(Grade::Good);
+        }
+        card_manager.previous_card();
+        card_manager.previous_card();
+        assert_eq!(
+            "0 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert_eq!(
+            "1 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_scheduled_next_card_returns_most_overdue() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(10), Sm2::new());
+
+        let now = SystemTime::now();
+        for (index, managed) in card_manager.unseen_cards.iter_mut().enumerate() {
+            managed.schedule.due = Some(now - Duration::from_secs(index as u64));
+        }
+
+        let card = card_manager.next_card().unwrap();
+        assert_eq!(
+            "9 - front".to_string(),
+            card.upgrade().unwrap().borrow().get_front()
+        );
+    }
+
+    #[test]
+    fn test_fsrs_scheduled_manager() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(3), Fsrs::default());
+
+        while card_manager.next_card().is_some() {
+            card_manager.grade_current_card(Grade::Easy);
+        }
+
+        assert_eq!(3, card_manager.num_of_cards_seen());
+        card_manager.add_previous_cards_to_deck();
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_grade_current_card() {
+        let mut card_manager = create_test_manager();
+
+        card_manager.grade_current_card(Grade::Good);
+        assert!(card_manager.current_card_grade().is_none());
+
+        let _ = card_manager.next_card();
+        card_manager.grade_current_card(Grade::Hard);
+        assert_eq!(Some(Grade::Hard), card_manager.current_card_grade());
+        assert_eq!(1, card_manager.num_of_cards_seen());
+    }
+
+    #[test]
+    fn test_failed_card_is_requeued() {
+        let mut card_manager = create_test_manager();
+
+        let failed_card = card_manager.next_card().unwrap();
+        card_manager.grade_current_card(Grade::Again);
+
+        assert_eq!(0, card_manager.num_of_cards_seen());
+        assert_eq!(10, card_manager.num_of_cards_in_deck());
+
+        let mut last_card = None;
+        while let Some(card) = card_manager.next_card() {
+            last_card = Some(card);
+        }
+        assert!(failed_card.ptr_eq(&last_card.unwrap()));
+    }
+
+    #[test]
+    fn test_failed_card_is_requeued_when_scheduled() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+
+        let failed_card = card_manager.next_card().unwrap();
+        card_manager.grade_current_card(Grade::Again);
+        let _ = card_manager.next_card().unwrap();
+        card_manager.grade_current_card(Grade::Good);
+
+        let card = card_manager.next_card().unwrap();
+        assert!(failed_card.ptr_eq(&card));
+        card_manager.grade_current_card(Grade::Good);
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_failed_card_requeue_disabled() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_requeue_failed_cards(false);
+
+        let _ = card_manager.next_card();
+        card_manager.grade_current_card(Grade::Again);
+
+        assert_eq!(1, card_manager.num_of_cards_seen());
+        assert_eq!(Some(Grade::Again), card_manager.current_card_grade());
+    }
+
+    #[test]
+    fn test_grade_records_review() {
+        let mut card_manager = create_test_manager();
+
+        let _ = card_manager.next_card();
+        card_manager.try_to_flip_current_card_to_hint();
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Hard);
+
+        let _ = card_manager.next_card();
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Good);
+
+        let _ = card_manager.previous_card();
+        let first_card = card_manager.previous_card().unwrap();
+        let first_card_id = first_card.upgrade().unwrap().borrow().get_id();
+
+        let reviews = card_manager.review_log().reviews();
+        assert_eq!(2, reviews.len());
+        assert_eq!(first_card_id, reviews[0].card_id);
+        assert_eq!(FlashCardState::Hint, reviews[0].state);
+        assert_eq!(Grade::Hard, reviews[0].grade);
+        assert_eq!(FlashCardState::Back, reviews[1].state);
+    }
+
+    #[test]
+    fn test_replay_review_log() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+
+        let mut log = ReviewLog::new();
+        log.record(Review::new(
+            crate::card::content_id("0 - front", "0 - back"),
+            SystemTime::now(),
+            Grade::Good,
+            Duration::from_secs(3),
+            FlashCardState::Back,
+        ));
+        card_manager.replay_review_log(log);
+
+        let card = card_manager.next_card().unwrap();
+        assert_eq!(
+            "1 - front".to_string(),
+            card.upgrade().unwrap().borrow().get_front()
+        );
+        assert!(card_manager.next_card().is_none());
+        assert_eq!(1, card_manager.review_log().len());
+    }
+
+    fn front_and_state(card: Option<Weak<RefCell<Card>>>) -> (String, FlashCardState) {
+        let card = card.unwrap().upgrade().unwrap();
+        let card = card.borrow();
+        (card.get_front(), *card.get_state())
+    }
+
+    #[test]
+    fn test_reverse_study_direction() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_study_direction(StudyDirection::Reverse);
+
+        let card = card_manager.next_card();
+        assert_eq!(
+            ("0 - front".to_string(), FlashCardState::Back),
+            front_and_state(card)
+        );
+
+        card_manager.flip_current_card();
+        assert_eq!(
+            FlashCardState::Front,
+            front_and_state(card_manager.current_card()).1
+        );
+        card_manager.reset_current_card_state();
+        assert_eq!(
+            FlashCardState::Back,
+            front_and_state(card_manager.current_card()).1
+        );
+
+        card_manager.grade_current_card(Grade::Good);
+        assert_eq!(
+            format!(
+                "{}:reverse",
+                crate::card::content_id("0 - front", "0 - back")
+            ),
+            card_manager.review_log().reviews()[0].card_id
+        );
+    }
+
+    #[test]
+    fn test_both_study_directions() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_study_direction(StudyDirection::Both);
+        assert_eq!(20, card_manager.num_of_cards_in_deck());
+
+        let card = card_manager.next_card();
+        assert_eq!(
+            ("0 - front".to_string(), FlashCardState::Front),
+            front_and_state(card)
+        );
+        card_manager.flip_current_card();
+
+        for _ in 0..9 {
+            card_manager.next_card();
+        }
+        let card = card_manager.next_card();
+        assert_eq!(
+            ("0 - front".to_string(), FlashCardState::Back),
+            front_and_state(card)
+        );
+
+        card_manager.set_study_direction(StudyDirection::Forward);
+        assert_eq!(
+            10,
+            card_manager.num_of_cards_seen() + card_manager.num_of_cards_in_deck()
+        );
+        assert_eq!(
+            FlashCardState::Front,
+            front_and_state(card_manager.current_card()).1
+        );
+    }
+
+    #[test]
+    fn test_both_study_directions_keep_their_own_side() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_study_direction(StudyDirection::Both);
+
+        let forward = card_manager.next_handle().unwrap();
+        card_manager.flip_current_card();
+        let reversed = card_manager
+            .handles()
+            .find(|handle| {
+                card_manager.is_reversed(*handle) == Some(true)
+                    && card_manager.get(*handle).unwrap().get_front() == "0 - front"
+            })
+            .unwrap();
+
+        let card = card_manager.jump_to(reversed);
+        assert_eq!(
+            ("0 - front".to_string(), FlashCardState::Back),
+            front_and_state(card)
+        );
+        card_manager.flip_current_card();
+        card_manager.flip_current_card();
+        card_manager.try_to_flip_current_card_to_hint();
+
+        card_manager.previous_card();
+        assert_eq!(Some(forward), card_manager.current_handle());
+        assert_eq!(
+            FlashCardState::Back,
+            front_and_state(card_manager.current_card()).1
+        );
+        assert_eq!(
+            FlashCardState::Hint,
+            card_manager.snapshot().unseen[0].state
+        );
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let mut card_manager = create_test_manager();
+
+        card_manager.next_card();
+        card_manager.try_to_flip_current_card_to_hint();
+        card_manager.try_to_flip_current_card_to_hint();
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Again);
+        card_manager.next_card();
+        let left = card_manager.next_card();
+        card_manager.previous_card();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.finish_session();
+
+        let left = front_and_state(left).0;
+        let left_id = crate::card::content_id(&left, &left.replace("front", "back"));
+        assert_eq!(
+            1,
+            card_manager
+                .stats()
+                .card(&left_id)
+                .unwrap()
+                .back_navigations
+        );
+
+        let card_0 = card_manager
+            .stats()
+            .card(&crate::card::content_id("0 - front", "0 - back"))
+            .unwrap();
+        assert_eq!(card_0.front, "0 - front".to_string());
+        assert_eq!((card_0.hints, card_0.flips), (1, 1));
+        assert_eq!(card_0.grades, vec![Grade::Again]);
+
+        let report = card_manager.stats().report(3);
+        assert_eq!(report.cards_studied, 3);
+        assert_eq!(report.accuracy, Some(0.5));
+        assert_eq!(report.back_navigations, 1);
+        assert_eq!(report.cards_needing_hints.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut card_manager = create_test_manager();
+        card_manager.shuffle_with_rng(&mut crate::seeded_rng(3));
+        card_manager.next_card();
+        card_manager.next_card();
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Hard);
+
+        let mut data = Vec::new();
+        card_manager.snapshot().write(&mut data).unwrap();
+        let snapshot = Snapshot::load(data.as_slice()).unwrap();
+
+        let mut restored = create_test_manager();
+        restored.restore(&snapshot).unwrap();
+
+        assert_eq!(snapshot, restored.snapshot());
+        assert_eq!(2, restored.num_of_cards_seen());
+        assert_eq!(Some(Grade::Hard), restored.current_card_grade());
+        let (front, state) = front_and_state(restored.current_card());
+        let (expected_front, expected_state) = front_and_state(card_manager.current_card());
+        assert_eq!(expected_front, front);
+        assert_eq!(expected_state, state);
+        assert_eq!(FlashCardState::Back, state);
+    }
+
+    #[test]
+    fn test_restore_against_changed_deck() {
+        let mut card_manager = create_test_manager();
+        card_manager.next_card();
+        let snapshot = card_manager.snapshot();
+
+        let mut smaller = create_sized_test_manager(9);
+        smaller.next_card();
+        assert_eq!(
+            Err(SnapshotError::UnknownCard {
+                id: crate::card::content_id("9 - front", "9 - back")
+            }),
+            smaller.restore(&snapshot)
+        );
+        assert_eq!(1, smaller.num_of_cards_seen());
+        assert_eq!(8, smaller.num_of_cards_in_deck());
+
+        let mut larger = create_sized_test_manager(11);
+        assert!(matches!(
+            larger.restore(&snapshot),
+            Err(SnapshotError::MissingCard { .. })
+        ));
+
+        // A failed restore leaves the study direction alone too.
+        card_manager.set_study_direction(StudyDirection::Both);
+        let snapshot = card_manager.snapshot();
+        let mut smaller = create_sized_test_manager(9);
+        assert!(smaller.restore(&snapshot).is_err());
+        assert_eq!(StudyDirection::Forward, smaller.study_direction());
+        assert_eq!(9, smaller.num_of_cards_in_deck());
+    }
+
+    #[test]
+    fn test_restore_keeps_pulled_cards() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+        card_manager.next_card();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.add_previous_cards_to_deck();
+        let handle = card_manager.search("0 - front")[0];
+        card_manager.move_to_front(handle);
+
+        let snapshot = card_manager.snapshot();
+        assert!(snapshot.unseen[0].pulled);
+        let mut restored =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+        restored.replay_review_log(std::mem::replace(
+            card_manager.review_log_mut(),
+            ReviewLog::new(),
+        ));
+        restored.restore(&snapshot).unwrap();
+
+        assert_eq!(
+            "0 - front".to_string(),
+            front_and_state(restored.next_card()).0
+        );
+    }
+
+    #[test]
+    fn test_resume_in_other_directions() {
+        // Only the card graded Good is not due again yet.
+        for (direction, due_cards) in [(StudyDirection::Reverse, 1), (StudyDirection::Both, 3)] {
+            let mut card_manager =
+                CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+            card_manager.set_study_direction(direction);
+            card_manager.next_card();
+            card_manager.grade_current_card(Grade::Good);
+            let snapshot = card_manager.snapshot();
+
+            let mut resumed =
+                CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+            resumed.replay_review_log(std::mem::replace(
+                card_manager.review_log_mut(),
+                ReviewLog::new(),
+            ));
+            resumed.restore(&snapshot).unwrap();
+
+            card_manager.add_previous_cards_to_deck();
+            resumed.add_previous_cards_to_deck();
+            let due = |manager: &mut CardsManager<Card, Sm2>| {
+                std::iter::from_fn(|| manager.next_handle()).count()
+            };
+            assert_eq!(due_cards, due(&mut card_manager));
+            assert_eq!(due_cards, due(&mut resumed));
+        }
+    }
+
+    #[test]
+    fn test_card_handles() {
+        let mut card_manager = create_test_manager();
+        assert_eq!(None, card_manager.current_handle());
+
+        let first = card_manager.next_handle().unwrap();
+        let second = card_manager.next_handle().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(Some(second), card_manager.current_handle());
+        assert_eq!(10, card_manager.handles().count());
+
+        card_manager.flip_current_card();
+        assert_eq!(
+            FlashCardState::Back,
+            *card_manager.get(second).unwrap().get_state()
+        );
+        assert_eq!(
+            "0 - front".to_string(),
+            card_manager.get(first).unwrap().get_front()
+        );
+
+        card_manager
+            .get_mut(second)
+            .unwrap()
+            .set_state(FlashCardState::Hint);
+        assert_eq!(Some(first), card_manager.previous_handle());
+        assert_eq!(Some(second), card_manager.next_handle());
+        assert_eq!(
+            FlashCardState::Hint,
+            *card_manager.get(second).unwrap().get_state()
+        );
+        assert_eq!(Some(first), card_manager.previous_handle());
+
+        card_manager.grade_current_card(Grade::Easy);
+        assert_eq!(Some(Grade::Easy), card_manager.grade(first));
+        assert_eq!(Some(false), card_manager.is_reversed(first));
+        assert!(card_manager.get(CardHandle(99)).is_none());
+    }
+
+    #[test]
+    fn test_card_handle_access_does_not_panic() {
+        let mut card_manager = create_test_manager();
+        let handle = card_manager.next_handle().unwrap();
+        let card = card_manager.current_card().unwrap().upgrade().unwrap();
+
+        let borrowed = card.borrow();
+        assert!(card_manager.get_mut(handle).is_none());
+        card_manager.flip_current_card();
+        card_manager.try_to_flip_current_card_to_hint();
+        card_manager.reset_current_card_state();
+        assert_eq!(FlashCardState::Front, *borrowed.get_state());
+        drop(borrowed);
+
+        let borrowed = card.borrow_mut();
+        assert!(card_manager.get(handle).is_none());
+        drop(borrowed);
+
+        assert!(card_manager.get(handle).is_some());
+    }
+
+    #[test]
+    fn test_manager_does_not_panic_on_borrowed_card() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_study_direction(StudyDirection::Reverse);
+        let first = card_manager.handle_at(0).unwrap();
+        let card = card_manager.cards().next().unwrap().upgrade().unwrap();
+
+        let borrowed = card.borrow();
+        assert_eq!(Some(first), card_manager.next_handle());
+        card_manager.previous_card();
+        card_manager.next_card();
+        drop(borrowed);
+        // The side is put on the card the next time it can be.
+        card_manager.reset_current_card_state();
+        assert_eq!(FlashCardState::Back, *card.borrow().get_state());
+
+        let borrowed = card.borrow_mut();
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.set_study_direction(StudyDirection::Both);
+        let snapshot = card_manager.snapshot();
+        card_manager.restore(&snapshot).unwrap();
+        drop(borrowed);
+
+        assert_eq!(1, card_manager.review_log().len());
+        assert_eq!(FlashCardState::Back, snapshot.seen[0].state);
+        assert_eq!(Some(Grade::Good), card_manager.current_card_grade());
+    }
+
+    #[test]
+    fn test_card_handles_with_both_directions() {
+        let mut card_manager = create_test_manager();
+        card_manager.set_study_direction(StudyDirection::Both);
+
+        let handles: HashSet<CardHandle> = card_manager.handles().collect();
+        assert_eq!(20, handles.len());
+        let reversed = card_manager
+            .handles()
+            .filter(|handle| card_manager.is_reversed(*handle) == Some(true))
+            .count();
+        assert_eq!(10, reversed);
+    }
+
+    #[test]
+    fn test_card_handles_survive_direction_changes() {
+        let mut card_manager = create_sized_test_manager(2);
+        let forward: Vec<CardHandle> = card_manager.handles().collect();
+        card_manager.set_study_direction(StudyDirection::Both);
+        let both: Vec<CardHandle> = card_manager.handles().collect();
+        card_manager.set_study_direction(StudyDirection::Both);
+
+        assert_eq!(4, card_manager.handles().count());
+        for handle in &both {
+            assert!(card_manager.get(*handle).is_some());
+            assert!(card_manager.position_of(*handle).is_some());
+        }
+
+        card_manager.set_study_direction(StudyDirection::Reverse);
+        assert_eq!(2, card_manager.handles().count());
+        assert!(card_manager
+            .handles()
+            .all(|handle| card_manager.is_reversed(handle) == Some(true)));
+        // Set aside, but still naming the same card and direction.
+        assert_eq!(Some(false), card_manager.is_reversed(forward[0]));
+        assert!(card_manager.get(forward[0]).is_some());
+        assert_eq!(None, card_manager.position_of(forward[0]));
+
+        card_manager.set_study_direction(StudyDirection::Forward);
+        assert_eq!(forward, card_manager.handles().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_search() {
+        let mut card_manager = create_test_manager();
+        card_manager.next_card();
+
+        let found = card_manager.search("3 - BACK");
+        assert_eq!(1, found.len());
+        assert_eq!(Some(3), card_manager.position_of(found[0]));
+        assert_eq!(1, card_manager.search("7 - hint").len());
+        assert_eq!(10, card_manager.search(" - ").len());
+        assert!(card_manager.search("missing").is_empty());
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let mut card_manager = create_test_manager();
+        card_manager.next_card();
+        card_manager.next_card();
+
+        let card = card_manager.jump_to_position(6);
+        assert_eq!("6 - front".to_string(), front_and_state(card).0);
+        assert_eq!(3, card_manager.num_of_cards_seen());
+
+        let card = card_manager.jump_to_id(&crate::card::content_id("0 - front", "0 - back"));
+        assert_eq!("0 - front".to_string(), front_and_state(card).0);
+        assert_eq!(3, card_manager.num_of_cards_seen());
+        assert_eq!(
+            Some(2),
+            card_manager.position_of(card_manager.current_handle().unwrap())
+        );
+
+        let card = card_manager.next_card();
+        assert_eq!("2 - front".to_string(), front_and_state(card).0);
+
+        assert!(card_manager.jump_to_position(10).is_none());
+        assert!(card_manager.jump_to_id("missing").is_none());
+    }
+
+    #[test]
+    fn test_move_to_front_when_scheduled() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(2), Sm2::new());
+        card_manager.next_card();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.add_previous_cards_to_deck();
+
+        let handle = card_manager.search("0 - front")[0];
+        assert!(card_manager.move_to_front(handle));
+        let card = card_manager.next_card();
+        assert_eq!("0 - front".to_string(), front_and_state(card).0);
+
+        let card = card_manager.next_card();
+        assert_eq!("1 - front".to_string(), front_and_state(card).0);
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_pulled_card_pushed_off_the_front() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(3), Sm2::new());
+        for _ in 0..3 {
+            card_manager.next_card();
+            card_manager.grade_current_card(Grade::Good);
+        }
+        card_manager.add_previous_cards_to_deck();
+
+        let handle = card_manager.search("0 - front")[0];
+        assert!(card_manager.move_to_front(handle));
+        let handle = card_manager.search("1 - front")[0];
+        assert!(card_manager.move_to_front(handle));
+        assert_eq!(
+            "1 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert!(card_manager.next_card().is_none());
+
+        let handle = card_manager.search("2 - front")[0];
+        assert!(card_manager.move_to_front(handle));
+        card_manager.previous_card();
+        assert_eq!(
+            "1 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_pulled_card_cleared_by_restart() {
+        let mut card_manager =
+            CardsManager::create_scheduled_from_deck(create_test_cards(3), Sm2::new());
+        card_manager.next_card();
+        card_manager.grade_current_card(Grade::Good);
+        card_manager.next_card();
+        let handle = card_manager.search("0 - front")[0];
+        assert!(card_manager.move_to_front(handle));
+        card_manager.add_previous_cards_to_deck();
+
+        assert_eq!(
+            "1 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert_eq!(
+            "2 - front".to_string(),
+            front_and_state(card_manager.next_card()).0
+        );
+        assert!(card_manager.next_card().is_none());
+    }
+
+    #[test]
+    fn test_search_does_not_panic_on_borrowed_card() {
+        let card_manager = create_test_manager();
+        let card = card_manager.cards().next().unwrap().upgrade().unwrap();
+        let card_id = card.borrow().get_id();
+
+        let borrowed = card.borrow_mut();
+        assert_eq!(9, card_manager.search(" - front").len());
+        assert_eq!(card_manager.handle_at(0), card_manager.find_by_id(&card_id));
+        drop(borrowed);
+
+        assert_eq!(10, card_manager.search(" - front").len());
+    }
 }
@@ -0,0 +1,318 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::traits::FlashCardsManager;
+use crate::{FlipFlashCard, Grade};
+
+const DEFAULT_MAX_DISTANCE: usize = 2;
+const DEFAULT_CHARS_PER_TYPO: usize = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Normalization {
+    pub ignore_case: bool,
+    pub ignore_whitespace: bool,
+    pub ignore_punctuation: bool,
+    pub ignore_diacritics: bool,
+}
+
+impl Normalization {
+    pub fn strict() -> Self {
+        Normalization {
+            ignore_case: false,
+            ignore_whitespace: false,
+            ignore_punctuation: false,
+            ignore_diacritics: false,
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut text: String = text.nfc().collect();
+
+        if self.ignore_diacritics {
+            text = text
+                .nfd()
+                .filter(|c| !is_combining_mark(*c))
+                .nfc()
+                .collect();
+        }
+        if self.ignore_case {
+            text = text.to_lowercase();
+        }
+        if self.ignore_punctuation {
+            text = text
+                .chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                .collect();
+        }
+        if self.ignore_whitespace {
+            // Runs of whitespace collapse to a single space so that word
+            // boundaries still count towards the edit distance.
+            text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+        }
+
+        text
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            ignore_case: true,
+            ignore_whitespace: true,
+            ignore_punctuation: true,
+            ignore_diacritics: true,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum AnswerResult {
+    Exact,
+    Close,
+    Wrong,
+}
+
+impl AnswerResult {
+    pub fn grade(&self) -> Grade {
+        match self {
+            AnswerResult::Exact => Grade::Good,
+            AnswerResult::Close => Grade::Hard,
+            AnswerResult::Wrong => Grade::Again,
+        }
+    }
+}
+
+impl Display for AnswerResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerResult::Exact => write!(f, "exact"),
+            AnswerResult::Close => write!(f, "close"),
+            AnswerResult::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Answer {
+    pub result: AnswerResult,
+    pub distance: usize,
+    pub expected: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Quiz {
+    normalization: Normalization,
+    max_distance: usize,
+    // Allows one typo for this many characters of the expected answer, up
+    // to max_distance. None allows max_distance whatever the length.
+    chars_per_typo: Option<usize>,
+}
+
+impl Quiz {
+    pub fn new(normalization: Normalization, max_distance: usize) -> Self {
+        Quiz {
+            normalization,
+            max_distance,
+            chars_per_typo: None,
+        }
+    }
+
+    pub fn set_chars_per_typo(&mut self, chars_per_typo: Option<usize>) {
+        self.chars_per_typo = chars_per_typo;
+    }
+
+    pub fn check(&self, answer: &str, expected: &str) -> Answer {
+        let normalized = self.normalization.apply(expected);
+        let distance = edit_distance(&self.normalization.apply(answer), &normalized);
+        let tolerance = match self.chars_per_typo {
+            Some(chars_per_typo) => self
+                .max_distance
+                .min(normalized.chars().count() / chars_per_typo.max(1)),
+            None => self.max_distance,
+        };
+        let result = match distance {
+            0 => AnswerResult::Exact,
+            distance if distance <= tolerance => AnswerResult::Close,
+            _ => AnswerResult::Wrong,
+        };
+
+        Answer {
+            result,
+            distance,
+            expected: expected.to_string(),
+        }
+    }
+
+    // Checks the answer against the back of the current card, or its front
+    // when the card is studied in reverse, and grades the card with the
+    // result so it ends up in the manager's review log.
+    pub fn answer_current_card<T: FlipFlashCard>(
+        &self,
+        manager: &mut dyn FlashCardsManager<T>,
+        answer: &str,
+    ) -> Option<Answer> {
+        let card = manager.current_card()?.upgrade()?;
+        let expected = match manager.is_current_card_reversed() {
+            true => card.try_borrow().ok()?.get_front(),
+            false => card.try_borrow().ok()?.get_back(),
+        };
+        let answer = self.check(answer, &expected);
+
+        manager.grade_current_card(answer.result.grade());
+        Some(answer)
+    }
+}
+
+impl Default for Quiz {
+    fn default() -> Self {
+        // Short answers get fewer typos, so that a two letter answer can't
+        // be all wrong and still count as close.
+        let mut quiz = Quiz::new(Normalization::default(), DEFAULT_MAX_DISTANCE);
+        quiz.set_chars_per_typo(Some(DEFAULT_CHARS_PER_TYPO));
+        quiz
+    }
+}
+
+// Levenshtein distance counted in characters rather than bytes.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Card, Cards, CardsManager, StudyDirection};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("paris", ""), 5);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("café", "cafe"), 1);
+    }
+
+    #[test]
+    fn test_normalization() {
+        let normalization = Normalization::default();
+        assert_eq!(normalization.apply("  ¿Ça  va,\tBIEN? "), "ca va bien");
+        assert_eq!(normalization.apply("Zoë"), "zoe");
+
+        let strict = Normalization::strict();
+        assert_eq!(strict.apply(" Ça va "), " Ça va ");
+
+        let case_only = Normalization {
+            ignore_case: true,
+            ..Normalization::strict()
+        };
+        assert_eq!(case_only.apply("Café!"), "café!");
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let quiz = Quiz::default();
+
+        assert_eq!(quiz.check("paris.", "Paris").result, AnswerResult::Exact);
+        assert_eq!(quiz.check("Pari", "Paris").result, AnswerResult::Close);
+        assert_eq!(quiz.check("Lyon", "Paris").result, AnswerResult::Wrong);
+
+        assert_eq!(quiz.check("Parsi", "Paris").result, AnswerResult::Wrong);
+        assert_eq!(
+            quiz.check("Marseile", "Marseille").result,
+            AnswerResult::Close
+        );
+
+        let answer = Quiz::new(Normalization::strict(), 0).check("paris", "Paris");
+        assert_eq!(answer.result, AnswerResult::Wrong);
+        assert_eq!(answer.distance, 1);
+        assert_eq!(answer.expected, "Paris".to_string());
+    }
+
+    #[test]
+    fn test_check_short_answer() {
+        let quiz = Quiz::default();
+
+        assert_eq!(quiz.check("", "No").result, AnswerResult::Wrong);
+        assert_eq!(quiz.check("ox", "an").result, AnswerResult::Wrong);
+        assert_eq!(quiz.check("on", "an").result, AnswerResult::Wrong);
+        assert_eq!(quiz.check("AN", "an").result, AnswerResult::Exact);
+        assert_eq!(quiz.check("cat", "cap").result, AnswerResult::Close);
+        assert_eq!(quiz.check("x", "").result, AnswerResult::Wrong);
+    }
+
+    #[test]
+    fn test_check_configured_tolerance() {
+        let mut quiz = Quiz::new(Normalization::default(), 5);
+
+        assert_eq!(quiz.check("Prs", "Paris").result, AnswerResult::Close);
+        assert_eq!(quiz.check("Pa", "Paris").result, AnswerResult::Close);
+
+        quiz.set_chars_per_typo(Some(2));
+        assert_eq!(quiz.check("Pars", "Paris").result, AnswerResult::Close);
+        assert_eq!(quiz.check("Prs", "Paris").result, AnswerResult::Close);
+        assert_eq!(quiz.check("Pa", "Paris").result, AnswerResult::Wrong);
+    }
+
+    #[test]
+    fn test_answer_current_card() {
+        let cards: Cards<Card> = [("France", "Paris"), ("Spain", "Madrid")]
+            .iter()
+            .map(|(front, back)| Card::new(front.to_string(), back.to_string(), String::new()))
+            .collect();
+        let mut manager = CardsManager::create_from_deck(cards);
+        let quiz = Quiz::default();
+
+        assert_eq!(quiz.answer_current_card(&mut manager, "Paris"), None);
+
+        manager.next_card();
+        let answer = quiz.answer_current_card(&mut manager, "paris").unwrap();
+        assert_eq!(answer.result, AnswerResult::Exact);
+        assert_eq!(manager.current_card_grade(), Some(Grade::Good));
+
+        manager.next_card();
+        let answer = quiz.answer_current_card(&mut manager, "Barcelona").unwrap();
+        assert_eq!(answer.result, AnswerResult::Wrong);
+
+        let grades: Vec<Grade> = manager
+            .review_log()
+            .reviews()
+            .iter()
+            .map(|review| review.grade)
+            .collect();
+        assert_eq!(grades, vec![Grade::Good, Grade::Again]);
+    }
+
+    #[test]
+    fn test_answer_current_card_in_reverse() {
+        let cards: Cards<Card> = [("France", "Paris")]
+            .iter()
+            .map(|(front, back)| Card::new(front.to_string(), back.to_string(), String::new()))
+            .collect();
+        let mut manager = CardsManager::create_from_deck(cards);
+        manager.set_study_direction(StudyDirection::Reverse);
+        let quiz = Quiz::default();
+
+        let card = manager.next_card().unwrap().upgrade().unwrap();
+        assert_eq!(card.borrow().to_string(), "Paris".to_string());
+
+        let answer = quiz.answer_current_card(&mut manager, "france").unwrap();
+        assert_eq!(answer.result, AnswerResult::Exact);
+        assert_eq!(answer.expected, "France".to_string());
+    }
+}
@@ -0,0 +1,168 @@
+use std::time::{Duration, SystemTime};
+
+use crate::enums::Grade;
+use crate::traits::Scheduler;
+
+pub(crate) const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+const INITIAL_EASE_FACTOR: f64 = 2.5;
+const MINIMUM_EASE_FACTOR: f64 = 1.3;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sm2State {
+    pub ease_factor: f64,
+    pub interval: u32,
+    pub repetitions: u32,
+    pub due: Option<SystemTime>,
+}
+
+impl Sm2State {
+    // A card that has never been reviewed is due straight away.
+    pub fn is_due(&self, now: SystemTime) -> bool {
+        match self.due {
+            None => true,
+            Some(due) => due <= now,
+        }
+    }
+}
+
+impl Default for Sm2State {
+    fn default() -> Self {
+        Sm2State {
+            ease_factor: INITIAL_EASE_FACTOR,
+            interval: 0,
+            repetitions: 0,
+            due: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Unscheduled {}
+
+impl Scheduler for Unscheduled {
+    type State = ();
+
+    fn review(&self, _state: &(), _grade: Grade, _now: SystemTime) {}
+
+    fn due(&self, _state: &()) -> Option<SystemTime> {
+        None
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Sm2 {}
+
+impl Sm2 {
+    pub fn new() -> Self {
+        Sm2 {}
+    }
+
+    // SM-2 grades answers on a 0-5 scale, where anything below 3 is a lapse.
+    fn quality(grade: Grade) -> u32 {
+        match grade {
+            Grade::Again => 1,
+            Grade::Hard => 3,
+            Grade::Good => 4,
+            Grade::Easy => 5,
+        }
+    }
+}
+
+impl Scheduler for Sm2 {
+    type State = Sm2State;
+
+    fn review(&self, state: &Sm2State, grade: Grade, now: SystemTime) -> Sm2State {
+        let quality = Sm2::quality(grade);
+
+        let (interval, repetitions) = if quality < 3 {
+            (1, 0)
+        } else {
+            let interval = match state.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (state.interval as f64 * state.ease_factor).round() as u32,
+            };
+            (interval, state.repetitions + 1)
+        };
+
+        let miss = (5 - quality) as f64;
+        let ease_factor =
+            (state.ease_factor + (0.1 - miss * (0.08 + miss * 0.02))).max(MINIMUM_EASE_FACTOR);
+
+        Sm2State {
+            ease_factor,
+            interval,
+            repetitions,
+            due: Some(now + Duration::from_secs(interval as u64 * SECONDS_PER_DAY)),
+        }
+    }
+
+    fn due(&self, state: &Sm2State) -> Option<SystemTime> {
+        state.due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(num: u64) -> Duration {
+        Duration::from_secs(num * SECONDS_PER_DAY)
+    }
+
+    #[test]
+    fn test_new_card_is_due() {
+        let state = Sm2State::default();
+
+        assert!(state.is_due(SystemTime::UNIX_EPOCH));
+        assert_eq!(state.ease_factor, INITIAL_EASE_FACTOR);
+    }
+
+    #[test]
+    fn test_good_intervals() {
+        let scheduler = Sm2::new();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let state = scheduler.review(&Sm2State::default(), Grade::Good, now);
+        assert_eq!(state.interval, 1);
+        assert_eq!(state.repetitions, 1);
+        assert_eq!(state.due, Some(now + days(1)));
+
+        let state = scheduler.review(&state, Grade::Good, now);
+        assert_eq!(state.interval, 6);
+
+        let state = scheduler.review(&state, Grade::Good, now);
+        assert_eq!(state.interval, 15);
+        assert_eq!(state.repetitions, 3);
+        assert_eq!(state.ease_factor, INITIAL_EASE_FACTOR);
+        assert!(!state.is_due(now + days(14)));
+        assert!(state.is_due(now + days(15)));
+    }
+
+    #[test]
+    fn test_again_resets_repetitions() {
+        let scheduler = Sm2::new();
+        let now = SystemTime::UNIX_EPOCH;
+
+        let state = scheduler.review(&Sm2State::default(), Grade::Easy, now);
+        let state = scheduler.review(&state, Grade::Easy, now);
+        assert_eq!(state.repetitions, 2);
+        assert!(state.ease_factor > INITIAL_EASE_FACTOR);
+
+        let state = scheduler.review(&state, Grade::Again, now);
+        assert_eq!(state.repetitions, 0);
+        assert_eq!(state.interval, 1);
+    }
+
+    #[test]
+    fn test_ease_factor_floor() {
+        let scheduler = Sm2::new();
+        let mut state = Sm2State::default();
+
+        for _ in 0..10 {
+            state = scheduler.review(&state, Grade::Again, SystemTime::UNIX_EPOCH);
+        }
+
+        assert_eq!(state.ease_factor, MINIMUM_EASE_FACTOR);
+    }
+}
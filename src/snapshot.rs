@@ -0,0 +1,136 @@
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{FlashCardState, Grade, StudyDirection};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct CardSnapshot {
+    pub card_id: String,
+    #[serde(default)]
+    pub reversed: bool,
+    pub state: FlashCardState,
+    pub last_grade: Option<Grade>,
+    #[serde(default)]
+    pub requeued: bool,
+    #[serde(default)]
+    pub hint_shown: bool,
+    #[serde(default)]
+    pub pulled: bool,
+    #[serde(default)]
+    pub sent_back: bool,
+}
+
+// The position of a CardsManager in its deck. The first seen card is the
+// current one. Schedules are not included, they are rebuilt from the review
+// log with CardsManager::replay_review_log.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub direction: StudyDirection,
+    pub seen: Vec<CardSnapshot>,
+    pub unseen: Vec<CardSnapshot>,
+}
+
+impl Snapshot {
+    pub fn current_card(&self) -> Option<&CardSnapshot> {
+        self.seen.first()
+    }
+
+    pub fn load(reader: impl std::io::Read) -> Result<Self, Error> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    pub fn write(&self, writer: impl std::io::Write) -> Result<(), Error> {
+        Ok(serde_json::to_writer_pretty(writer, self)?)
+    }
+
+    // A missing file means there is no session to resume.
+    pub fn open(path: impl AsRef<Path>) -> Result<Option<Self>, Error> {
+        match std::fs::File::open(path) {
+            Ok(file) => Snapshot::load(file).map(Some),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    // Written next to the target first and then renamed over it, so a crash
+    // while saving leaves the previous snapshot intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let temp_path = path.with_extension("tmp");
+
+        let file = std::fs::File::create(&temp_path)?;
+        self.write(&file)?;
+        file.sync_all()?;
+
+        std::fs::rename(&temp_path, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_snapshot() -> Snapshot {
+        Snapshot {
+            direction: StudyDirection::Forward,
+            seen: vec![CardSnapshot {
+                card_id: "card-1".to_string(),
+                reversed: false,
+                state: FlashCardState::Back,
+                last_grade: Some(Grade::Good),
+                requeued: false,
+                hint_shown: true,
+                pulled: false,
+                sent_back: false,
+            }],
+            unseen: vec![CardSnapshot {
+                card_id: "card-2".to_string(),
+                reversed: false,
+                state: FlashCardState::Front,
+                last_grade: None,
+                requeued: false,
+                hint_shown: false,
+                pulled: true,
+                sent_back: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_write_and_load() {
+        let snapshot = create_test_snapshot();
+        let mut data = Vec::new();
+
+        snapshot.write(&mut data).unwrap();
+        let loaded = Snapshot::load(data.as_slice()).unwrap();
+
+        assert_eq!(snapshot, loaded);
+        assert_eq!(loaded.current_card().unwrap().card_id, "card-1".to_string());
+    }
+
+    #[test]
+    fn test_save_and_open() {
+        let path =
+            std::env::temp_dir().join(format!("flash_cards_{}_snapshot.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(Snapshot::open(&path).unwrap(), None);
+
+        let snapshot = create_test_snapshot();
+        snapshot.save(&path).unwrap();
+        let opened = Snapshot::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(opened, Some(snapshot));
+    }
+
+    #[test]
+    fn test_load_invalid_snapshot() {
+        let err = Snapshot::load("{\"seen\": 3}".as_bytes()).err().unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::Grade;
+
+#[derive(Serialize, Debug, Default, PartialEq, Clone)]
+pub struct CardStats {
+    pub card_id: String,
+    pub front: String,
+    pub views: u32,
+    pub flips: u32,
+    pub hints: u32,
+    pub back_navigations: u32,
+    pub time_spent_ms: u64,
+    pub grades: Vec<Grade>,
+}
+
+impl CardStats {
+    pub fn time_spent(&self) -> Duration {
+        Duration::from_millis(self.time_spent_ms)
+    }
+
+    pub fn last_grade(&self) -> Option<Grade> {
+        self.grades.last().copied()
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Clone)]
+pub struct SessionReport {
+    pub cards_studied: usize,
+    pub reviews: usize,
+    pub correct: usize,
+    // None until at least one card has been graded.
+    pub accuracy: Option<f64>,
+    pub time_spent_ms: u64,
+    pub flips: u32,
+    pub hints: u32,
+    pub back_navigations: u32,
+    pub slowest_cards: Vec<CardStats>,
+    pub cards_needing_hints: Vec<CardStats>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SessionStats {
+    cards: Vec<CardStats>,
+    index: HashMap<String, usize>,
+    // The card on screen and when it was put there.
+    current: Option<(usize, Instant)>,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        SessionStats::default()
+    }
+
+    pub fn cards(&self) -> &[CardStats] {
+        &self.cards
+    }
+
+    pub fn card(&self, card_id: &str) -> Option<&CardStats> {
+        self.index.get(card_id).map(|index| &self.cards[*index])
+    }
+
+    fn entry(&mut self, card_id: &str, front: &str) -> usize {
+        match self.index.get(card_id) {
+            Some(index) => {
+                if self.cards[*index].front.is_empty() {
+                    self.cards[*index].front = front.to_string();
+                }
+                *index
+            }
+            None => {
+                self.cards.push(CardStats {
+                    card_id: card_id.to_string(),
+                    front: front.to_string(),
+                    ..Default::default()
+                });
+                self.index.insert(card_id.to_string(), self.cards.len() - 1);
+                self.cards.len() - 1
+            }
+        }
+    }
+
+    fn current_mut(&mut self) -> Option<&mut CardStats> {
+        let (index, _) = self.current?;
+        self.cards.get_mut(index)
+    }
+
+    // Moves the clock over to another card, adding the time spent on the
+    // previous one. Showing the card that is already current changes nothing.
+    pub(crate) fn show(&mut self, card: Option<(&str, &str)>, now: Instant) {
+        let index = card.map(|(card_id, front)| self.entry(card_id, front));
+        if let Some((current, shown_at)) = self.current {
+            if Some(current) == index {
+                return;
+            }
+            let elapsed = now.saturating_duration_since(shown_at).as_millis() as u64;
+            self.cards[current].time_spent_ms += elapsed;
+        }
+
+        self.current = index.map(|index| (index, now));
+    }
+
+    pub(crate) fn record_view(&mut self) {
+        if let Some(card) = self.current_mut() {
+            card.views += 1;
+        }
+    }
+
+    pub(crate) fn record_flip(&mut self) {
+        if let Some(card) = self.current_mut() {
+            card.flips += 1;
+        }
+    }
+
+    pub(crate) fn record_hint(&mut self) {
+        if let Some(card) = self.current_mut() {
+            card.hints += 1;
+        }
+    }
+
+    pub(crate) fn record_back_navigation(&mut self) {
+        if let Some(card) = self.current_mut() {
+            card.back_navigations += 1;
+        }
+    }
+
+    pub(crate) fn record_grade(&mut self, grade: Grade) {
+        if let Some(card) = self.current_mut() {
+            card.grades.push(grade);
+        }
+    }
+
+    // Time on the card still on screen is only counted once it is left, so
+    // `show(None, now)` should be called first for an up to date report.
+    pub fn report(&self, limit: usize) -> SessionReport {
+        let grades = self.cards.iter().flat_map(|card| card.grades.iter());
+        let reviews = grades.clone().count();
+        let correct = grades.filter(|grade| **grade != Grade::Again).count();
+
+        let mut slowest_cards: Vec<CardStats> = self
+            .cards
+            .iter()
+            .filter(|card| card.time_spent_ms > 0)
+            .cloned()
+            .collect();
+        slowest_cards.sort_by_key(|card| std::cmp::Reverse(card.time_spent_ms));
+        slowest_cards.truncate(limit);
+
+        SessionReport {
+            cards_studied: self.cards.iter().filter(|card| card.views > 0).count(),
+            reviews,
+            correct,
+            accuracy: match reviews {
+                0 => None,
+                reviews => Some(correct as f64 / reviews as f64),
+            },
+            time_spent_ms: self.cards.iter().map(|card| card.time_spent_ms).sum(),
+            flips: self.cards.iter().map(|card| card.flips).sum(),
+            hints: self.cards.iter().map(|card| card.hints).sum(),
+            back_navigations: self.cards.iter().map(|card| card.back_navigations).sum(),
+            slowest_cards,
+            cards_needing_hints: self
+                .cards
+                .iter()
+                .filter(|card| card.hints > 0)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_spent() {
+        let start = Instant::now();
+        let mut stats = SessionStats::new();
+
+        stats.show(Some(("a", "A")), start);
+        stats.show(Some(("a", "A")), start + Duration::from_secs(1));
+        stats.show(Some(("b", "B")), start + Duration::from_secs(3));
+        stats.show(Some(("a", "A")), start + Duration::from_secs(4));
+        stats.show(None, start + Duration::from_secs(6));
+
+        assert_eq!(
+            stats.card("a").unwrap().time_spent(),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            stats.card("b").unwrap().time_spent(),
+            Duration::from_secs(1)
+        );
+        assert!(stats.card("c").is_none());
+    }
+
+    #[test]
+    fn test_report() {
+        let start = Instant::now();
+        let mut stats = SessionStats::new();
+
+        stats.show(Some(("a", "A")), start);
+        stats.record_view();
+        stats.record_hint();
+        stats.record_flip();
+        stats.record_grade(Grade::Again);
+        stats.show(Some(("b", "B")), start + Duration::from_secs(2));
+        stats.record_view();
+        stats.record_grade(Grade::Good);
+        stats.record_back_navigation();
+        stats.show(Some(("a", "A")), start + Duration::from_secs(3));
+        stats.record_view();
+        stats.record_grade(Grade::Good);
+        stats.show(None, start + Duration::from_secs(7));
+
+        let report = stats.report(1);
+
+        assert_eq!(report.cards_studied, 2);
+        assert_eq!(report.reviews, 3);
+        assert_eq!(report.correct, 2);
+        assert_eq!(report.accuracy, Some(2.0 / 3.0));
+        assert_eq!(report.time_spent_ms, 7000);
+        assert_eq!(report.flips, 1);
+        assert_eq!(report.back_navigations, 1);
+        assert_eq!(report.slowest_cards.len(), 1);
+        assert_eq!(report.slowest_cards[0].card_id, "a".to_string());
+        assert_eq!(
+            report.cards_needing_hints[0].last_grade(),
+            Some(Grade::Good)
+        );
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""accuracy":0.6666666666666666"#));
+        assert!(json.contains(r#""grades":["again","good"]"#));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = SessionStats::new().report(5);
+
+        assert_eq!(report.accuracy, None);
+        assert!(report.slowest_cards.is_empty());
+    }
+}
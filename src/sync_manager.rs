@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+
+use crate::manager::GenericCardsManager;
+use crate::scheduler::Unscheduled;
+use crate::traits::{CardCell, CardRef, FlipFlashCard};
+
+impl<T> CardRef<T> for Weak<Mutex<T>> {
+    fn read<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let card = self.upgrade()?;
+        let card = card.lock().unwrap_or_else(PoisonError::into_inner);
+        Some(f(&card))
+    }
+}
+
+impl<T: FlipFlashCard> CardCell for Arc<Mutex<T>> {
+    type Card = T;
+    type Weak = Weak<Mutex<T>>;
+    type Ref<'a>
+        = MutexGuard<'a, T>
+    where
+        T: 'a;
+    type RefMut<'a>
+        = MutexGuard<'a, T>
+    where
+        T: 'a;
+
+    fn new(card: T) -> Self {
+        Arc::new(Mutex::new(card))
+    }
+
+    fn downgrade(&self) -> Self::Weak {
+        Arc::downgrade(self)
+    }
+
+    // Other threads only hold a card briefly, so this waits for them rather
+    // than dropping what the user asked for. A card that panicked while
+    // locked is still usable, so poisoning is ignored.
+    fn get_card(&self) -> Option<Self::Ref<'_>> {
+        Some(self.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn get_card_mut(&self) -> Option<Self::RefMut<'_>> {
+        self.get_card()
+    }
+
+    fn as_ptr(&self) -> *const () {
+        Arc::as_ptr(self) as *const ()
+    }
+}
+
+// Thread-safe counterpart of CardsManager, with the same operations. It is
+// Send and Sync whenever the cards and scheduler are, so sessions can live
+// in a multi-threaded server. Cards are shared as Weak<Mutex<T>>.
+pub type SyncCardsManager<T, S = Unscheduled> = GenericCardsManager<Arc<Mutex<T>>, S>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::tests::create_test_cards;
+    use crate::{Card, FlashCard, FlashCardState, FlashCardsManager, Grade, Sm2, StudyDirection};
+    use std::thread;
+
+    fn lock<T>(card: &Mutex<T>) -> MutexGuard<'_, T> {
+        card.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn create_test_manager(size: usize) -> SyncCardsManager<Card> {
+        SyncCardsManager::create_from_deck(create_test_cards(size))
+    }
+
+    fn front(card: Option<Weak<Mutex<Card>>>) -> String {
+        lock(&card.unwrap().upgrade().unwrap()).get_front()
+    }
+
+    #[test]
+    fn test_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SyncCardsManager<Card>>();
+        assert_send_sync::<SyncCardsManager<Card, Sm2>>();
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut card_manager = create_test_manager(10);
+
+        assert_eq!("0 - front".to_string(), front(card_manager.next_card()));
+        assert_eq!("1 - front".to_string(), front(card_manager.next_card()));
+        card_manager.flip_current_card();
+        assert_eq!(
+            FlashCardState::Back,
+            *lock(&card_manager.current_card().unwrap().upgrade().unwrap()).get_state()
+        );
+        card_manager.reset_current_card_state();
+        card_manager.try_to_flip_current_card_to_hint();
+        assert_eq!(
+            "1 - hint".to_string(),
+            lock(&card_manager.current_card().unwrap().upgrade().unwrap()).to_string()
+        );
+
+        assert_eq!("1 - front".to_string(), front(card_manager.previous_card()));
+        assert_eq!("0 - front".to_string(), front(card_manager.current_card()));
+        card_manager.add_previous_cards_to_deck();
+        assert_eq!(0, card_manager.num_of_cards_seen());
+        assert_eq!(10, card_manager.num_of_cards_in_deck());
+    }
+
+    #[test]
+    fn test_grade_current_card() {
+        let mut card_manager = create_test_manager(10);
+        card_manager.next_card();
+        card_manager.grade_current_card(Grade::Again);
+
+        assert_eq!(0, card_manager.num_of_cards_seen());
+        assert_eq!(1, card_manager.review_log().len());
+        assert_eq!("1 - front".to_string(), front(card_manager.next_card()));
+        card_manager.grade_current_card(Grade::Good);
+        assert_eq!(Some(Grade::Good), card_manager.current_card_grade());
+    }
+
+    #[test]
+    fn test_shared_operations() {
+        let mut card_manager = create_test_manager(10);
+        card_manager.set_study_direction(StudyDirection::Reverse);
+
+        assert_eq!("0 - front".to_string(), front(card_manager.next_card()));
+        assert!(card_manager.is_current_card_reversed());
+        let found = card_manager.search("4 - back");
+        assert_eq!(Some(4), card_manager.position_of(found[0]));
+        assert_eq!(
+            "4 - front".to_string(),
+            front(card_manager.jump_to(found[0]))
+        );
+        card_manager.grade_current_card(Grade::Good);
+
+        let snapshot = card_manager.snapshot();
+        let mut restored = create_test_manager(10);
+        restored.restore(&snapshot).unwrap();
+        assert_eq!(snapshot, restored.snapshot());
+        assert_eq!(StudyDirection::Reverse, restored.study_direction());
+
+        card_manager.finish_session();
+        assert_eq!(2, card_manager.stats().report(3).cards_studied);
+    }
+
+    #[test]
+    fn test_card_locked_by_another_thread() {
+        let mut card_manager = create_test_manager(10);
+        card_manager.next_card();
+        let card = card_manager.cards().next().unwrap().upgrade().unwrap();
+
+        let (locked, release) = std::sync::mpsc::channel();
+        let other = thread::spawn(move || {
+            let _card = lock(&card);
+            locked.send(()).unwrap();
+            thread::sleep(std::time::Duration::from_millis(50));
+        });
+        release.recv().unwrap();
+
+        // Waits for the other thread instead of dropping the flip.
+        card_manager.flip_current_card();
+        card_manager.grade_current_card(Grade::Good);
+        other.join().unwrap();
+
+        let reviews = card_manager.review_log().reviews();
+        assert_eq!(FlashCardState::Back, reviews[0].state);
+        assert_eq!(1, card_manager.search("0 - front").len());
+    }
+
+    #[test]
+    fn test_poisoned_card() {
+        let mut card_manager = create_test_manager(10);
+        card_manager.next_card();
+        let card = card_manager.cards().next().unwrap().upgrade().unwrap();
+
+        let _ = thread::spawn(move || {
+            let _card = lock(&card);
+            panic!("poisons the card");
+        })
+        .join();
+
+        card_manager.flip_current_card();
+        assert_eq!(
+            FlashCardState::Back,
+            *lock(&card_manager.current_card().unwrap().upgrade().unwrap()).get_state()
+        );
+    }
+
+    #[test]
+    fn test_sessions_on_other_threads() {
+        let sessions: Vec<Arc<Mutex<SyncCardsManager<Card>>>> = (0..4)
+            .map(|_| Arc::new(Mutex::new(create_test_manager(10))))
+            .collect();
+
+        let handles: Vec<_> = sessions
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, session)| {
+                thread::spawn(move || {
+                    let mut card_manager = lock(&session);
+                    for _ in 0..=index {
+                        card_manager.next_card();
+                    }
+                    card_manager.grade_current_card(Grade::Good);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for (index, session) in sessions.iter().enumerate() {
+            let card_manager = lock(session);
+            assert_eq!(index + 1, card_manager.num_of_cards_seen());
+            assert_eq!(1, card_manager.review_log().len());
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use crate::errors::{LoadError, Position};
+use crate::loader::{read_text, CardChecker};
+use crate::{Card, Cards, FlashCards, Loader};
+
+const HINT_PREFIX: &str = "Hint:";
+const QUESTION_PREFIX: &str = "Q:";
+const ANSWER_PREFIX: &str = "A:";
+
+fn parse_error(line: usize, message: String) -> LoadError {
+    LoadError::Malformed {
+        position: Position::line(line as u64),
+        message,
+    }
+}
+
+struct PendingCard {
+    line: usize,
+    front: String,
+    back: Vec<String>,
+    hint: Option<String>,
+    has_answer: bool,
+}
+
+impl PendingCard {
+    fn new(line: usize, front: &str) -> Self {
+        PendingCard {
+            line,
+            front: front.trim().to_string(),
+            back: Vec::new(),
+            hint: None,
+            has_answer: false,
+        }
+    }
+
+    fn set_hint(&mut self, line: usize, hint: &str) -> Result<(), LoadError> {
+        if self.hint.is_some() {
+            return Err(parse_error(
+                line,
+                format!("card \"{}\" already has a hint", self.front),
+            ));
+        }
+
+        self.hint = Some(hint.trim().to_string());
+        Ok(())
+    }
+
+    fn finish(self, checker: &mut CardChecker) -> Result<Card, LoadError> {
+        let back = self.back.join("\n").trim().to_string();
+
+        let mut card = Card::new(self.front, back, String::new());
+        card.set_hint(self.hint.filter(|hint| !hint.is_empty()));
+
+        checker.check(&card, Position::line(self.line as u64))?;
+        Ok(card)
+    }
+}
+
+struct TextDeck {
+    cards: Cards<Card>,
+    checker: CardChecker,
+}
+
+impl TextDeck {
+    fn new() -> Self {
+        TextDeck {
+            cards: Cards::new(),
+            checker: CardChecker::new(),
+        }
+    }
+
+    fn finish(&mut self, pending: Option<PendingCard>) -> Result<(), LoadError> {
+        if let Some(pending) = pending {
+            let card = pending.finish(&mut self.checker)?;
+            self.cards.add_card(card);
+        }
+        Ok(())
+    }
+}
+
+// Headings are card fronts, the text below them is the back and an optional
+// "Hint:" line holds the hint:
+//
+// # What is the capital of France?
+// Paris
+// Hint: The Eiffel Tower
+pub struct Markdown {}
+
+impl Markdown {
+    fn heading(line: &str) -> Option<&str> {
+        let text = line.trim_start_matches('#');
+        let level = line.len() - text.len();
+
+        if (1..=6).contains(&level) && (text.is_empty() || text.starts_with(' ')) {
+            Some(text)
+        } else {
+            None
+        }
+    }
+
+    fn fence(line: &str) -> Option<&'static str> {
+        let line = line.trim_start();
+        ["```", "~~~"]
+            .into_iter()
+            .find(|fence| line.starts_with(fence))
+    }
+}
+
+impl Loader<Card> for Markdown {
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<Card>>, LoadError> {
+        let data = read_text(reader)?;
+        let mut deck = TextDeck::new();
+        let mut pending: Option<PendingCard> = None;
+        // Lines inside a fenced code block belong to the back as they are,
+        // even when they look like a heading or a hint.
+        let mut open_fence: Option<&str> = None;
+
+        for (index, line) in data.lines().enumerate() {
+            let line_num = index + 1;
+
+            let fence = Markdown::fence(line);
+            let in_fence = open_fence.is_some() || fence.is_some();
+            match open_fence {
+                None => open_fence = fence,
+                Some(open) if fence == Some(open) => open_fence = None,
+                Some(_) => {}
+            }
+
+            if let Some(front) = Markdown::heading(line).filter(|_| !in_fence) {
+                deck.finish(pending.take())?;
+                pending = Some(PendingCard::new(line_num, front));
+                continue;
+            }
+
+            match pending.as_mut() {
+                None if line.trim().is_empty() => {}
+                None => {
+                    return Err(parse_error(
+                        line_num,
+                        "text found before the first heading".to_string(),
+                    ))
+                }
+                Some(card) if in_fence => card.back.push(line.to_string()),
+                Some(card) => match line.strip_prefix(HINT_PREFIX) {
+                    Some(hint) => card.set_hint(line_num, hint)?,
+                    None => card.back.push(line.to_string()),
+                },
+            }
+        }
+
+        deck.finish(pending)?;
+        Ok(Box::new(deck.cards))
+    }
+}
+
+// Cards are written as "Q:" and "A:" pairs, where the answer may continue
+// over several lines, followed by an optional "Hint:" line:
+//
+// Q: What is the capital of France?
+// A: Paris
+// Hint: The Eiffel Tower
+pub struct PlainText {}
+
+impl PlainText {
+    fn finish_question(deck: &mut TextDeck, pending: Option<PendingCard>) -> Result<(), LoadError> {
+        if let Some(card) = &pending {
+            if !card.has_answer {
+                return Err(parse_error(
+                    card.line,
+                    format!("question \"{}\" has no \"A:\" line", card.front),
+                ));
+            }
+        }
+        deck.finish(pending)
+    }
+}
+
+impl Loader<Card> for PlainText {
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<Card>>, LoadError> {
+        let data = read_text(reader)?;
+        let mut deck = TextDeck::new();
+        let mut pending: Option<PendingCard> = None;
+
+        for (index, line) in data.lines().enumerate() {
+            let line_num = index + 1;
+
+            if let Some(front) = line.strip_prefix(QUESTION_PREFIX) {
+                PlainText::finish_question(&mut deck, pending.take())?;
+                pending = Some(PendingCard::new(line_num, front));
+                continue;
+            }
+
+            let card = match pending.as_mut() {
+                None if line.trim().is_empty() => continue,
+                None => {
+                    return Err(parse_error(
+                        line_num,
+                        "expected a line starting with \"Q:\"".to_string(),
+                    ))
+                }
+                Some(card) => card,
+            };
+
+            if let Some(answer) = line.strip_prefix(ANSWER_PREFIX) {
+                if card.has_answer {
+                    return Err(parse_error(
+                        line_num,
+                        format!("question \"{}\" already has an answer", card.front),
+                    ));
+                }
+                card.has_answer = true;
+                card.back.push(answer.trim().to_string());
+            } else if let Some(hint) = line.strip_prefix(HINT_PREFIX) {
+                card.set_hint(line_num, hint)?;
+            } else if card.has_answer {
+                card.back.push(line.to_string());
+            } else if !line.trim().is_empty() {
+                card.front = format!("{}\n{}", card.front, line.trim());
+            }
+        }
+
+        PlainText::finish_question(&mut deck, pending)?;
+        Ok(Box::new(deck.cards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FlashCard;
+
+    #[test]
+    fn test_markdown_reader() {
+        let data = "\
+# What is the capital of France?
+Paris
+Hint: The Eiffel Tower
+
+## Name two primary colours
+
+- red
+- blue
+";
+        let mut result = Markdown::load(data.as_bytes()).unwrap();
+
+        let card_1 = result.draw().unwrap();
+        let card_2 = result.draw().unwrap();
+
+        assert_eq!(
+            card_1.get_front(),
+            "What is the capital of France?".to_string()
+        );
+        assert_eq!(card_1.get_back(), "Paris".to_string());
+        assert_eq!(card_1.get_hint(), Some("The Eiffel Tower".to_string()));
+        assert_eq!(card_2.get_back(), "- red\n- blue".to_string());
+        assert_eq!(card_2.get_hint(), None);
+        assert!(result.draw().is_none());
+    }
+
+    #[test]
+    fn test_markdown_reader_code_blocks() {
+        let data = "\
+# How to list files?
+```sh
+# list all
+ls -a
+```
+
+# How to print a file?
+~~~
+# ``` is not the end
+Hint: cat
+~~~
+Hint: concatenate
+";
+        let mut result = Markdown::load(data.as_bytes()).unwrap();
+
+        let card_1 = result.draw().unwrap();
+        let card_2 = result.draw().unwrap();
+
+        assert_eq!(card_1.get_front(), "How to list files?".to_string());
+        assert_eq!(
+            card_1.get_back(),
+            "```sh\n# list all\nls -a\n```".to_string()
+        );
+        assert_eq!(
+            card_2.get_back(),
+            "~~~\n# ``` is not the end\nHint: cat\n~~~".to_string()
+        );
+        assert_eq!(card_2.get_hint(), Some("concatenate".to_string()));
+        assert!(result.draw().is_none());
+    }
+
+    #[test]
+    fn test_markdown_reader_errors() {
+        let data = "Intro text\n# Front\nBack\n";
+        let err = Markdown::load(data.as_bytes()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 1: text found before the first heading"
+        );
+
+        let data = "# Front\nBack\n\n# Empty\n\n# Other\nBack\n";
+        let err = Markdown::load(data.as_bytes()).err().unwrap();
+        assert_eq!(err.to_string(), "line 4: `back` is empty");
+
+        // Repeated cards are for the Validator to report.
+        let data = "# Front\nBack\n\n# Front\nBack\n";
+        assert_eq!(2, Markdown::load(data.as_bytes()).unwrap().deck_size());
+
+        let data = b"# Front\nBack\n# \xff\n";
+        let err = Markdown::load(&data[..]).err().unwrap();
+        assert_eq!(err.to_string(), "line 3: invalid UTF-8");
+
+        let data = "# Front\nBack\nHint: one\nHint: two\n";
+        let err = Markdown::load(data.as_bytes()).err().unwrap();
+        assert_eq!(err.to_string(), "line 4: card \"Front\" already has a hint");
+    }
+
+    #[test]
+    fn test_markdown_heading() {
+        assert_eq!(Markdown::heading("# Front"), Some(" Front"));
+        assert_eq!(Markdown::heading("###### Front"), Some(" Front"));
+        assert_eq!(Markdown::heading("####### Front"), None);
+        assert_eq!(Markdown::heading("#hashtag"), None);
+        assert_eq!(Markdown::heading("Front"), None);
+    }
+
+    #[test]
+    fn test_plain_text_reader() {
+        let data = "\
+Q: What is the capital of France?
+A: Paris
+Hint: The Eiffel Tower
+
+Q: Name two primary colours
+A: red
+blue
+";
+        let mut result = PlainText::load(data.as_bytes()).unwrap();
+
+        let card_1 = result.draw().unwrap();
+        let card_2 = result.draw().unwrap();
+
+        assert_eq!(card_1.get_back(), "Paris".to_string());
+        assert_eq!(card_1.get_hint(), Some("The Eiffel Tower".to_string()));
+        assert_eq!(card_2.get_front(), "Name two primary colours".to_string());
+        assert_eq!(card_2.get_back(), "red\nblue".to_string());
+    }
+
+    #[test]
+    fn test_plain_text_reader_errors() {
+        let data = "A: Paris\n";
+        let err = PlainText::load(data.as_bytes()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 1: expected a line starting with \"Q:\""
+        );
+
+        let data = "Q: France\nA: Paris\n\nQ: Germany\n\nQ: Spain\nA: Madrid\n";
+        let err = PlainText::load(data.as_bytes()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 4: question \"Germany\" has no \"A:\" line"
+        );
+
+        let data = "Q: France\nA: Paris\nA: Lyon\n";
+        let err = PlainText::load(data.as_bytes()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 3: question \"France\" already has an answer"
+        );
+
+        let data = "Q: France\n";
+        let err = PlainText::load(data.as_bytes()).err().unwrap();
+        assert_eq!(err.position(), Some(&Position::line(1)));
+
+        let data = "Q:\nA: Paris\n";
+        let err = PlainText::load(data.as_bytes()).err().unwrap();
+        assert!(matches!(err, LoadError::EmptyField { field, .. } if field == "front"));
+    }
+}
@@ -1,14 +1,25 @@
 use std::cell::RefCell;
 use std::fmt::{Debug, Display};
 use std::io::Error;
+use std::ops::{Deref, DerefMut};
 use std::rc::Weak;
+use std::time::SystemTime;
 
-use crate::FlashCardState;
+use rand::{thread_rng, RngCore};
+
+use crate::card::content_id;
+use crate::errors::LoadError;
+use crate::stats::SessionStats;
+use crate::{FlashCardState, Grade};
 
 pub trait FlashCard<'de>: serde::Deserialize<'de> + Display + Debug {
     fn get_front(&self) -> String;
     fn get_back(&self) -> String;
     fn get_hint(&self) -> Option<String>;
+
+    fn get_id(&self) -> String {
+        content_id(&self.get_front(), &self.get_back())
+    }
 }
 
 pub trait FlipFlashCard: for<'de> FlashCard<'de> {
@@ -21,27 +32,134 @@ pub trait FlashCards<T>: Display
 where
     T: for<'de> FlashCard<'de>,
 {
-    fn shuffle(&mut self);
+    fn shuffle(&mut self) {
+        self.shuffle_with_rng(&mut thread_rng());
+    }
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore);
     fn draw(&mut self) -> Option<T>;
     fn add_card(&mut self, new_card: T);
     fn add_card_to_top(&mut self, new_card: T);
     fn deck_size(&self) -> usize;
     fn add_deck(&mut self, deck: Box<dyn FlashCards<T>>);
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
 }
 
 pub trait Loader<T: for<'de> FlashCard<'de>> {
-    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, Error>;
+    fn load(reader: impl std::io::Read) -> Result<Box<dyn FlashCards<T>>, LoadError>;
+}
+
+pub trait Saver<T: for<'de> FlashCard<'de> + serde::Serialize> {
+    fn save(deck: &dyn FlashCards<T>, writer: impl std::io::Write) -> Result<(), Error>;
 }
 
-pub trait FlashCardsManager<T: FlipFlashCard> {
-    fn next_card(&mut self) -> Option<Weak<RefCell<T>>>;
-    fn current_card(&mut self) -> Option<Weak<RefCell<T>>>;
+// W is the reference handed out for a card, Weak<RefCell<T>> unless the
+// manager is shared between threads.
+pub trait FlashCardsManager<T: FlipFlashCard, W = Weak<RefCell<T>>> {
+    fn next_card(&mut self) -> Option<W>;
+    fn current_card(&mut self) -> Option<W>;
     fn flip_current_card(&mut self);
     fn try_to_flip_current_card_to_hint(&mut self);
     fn reset_current_card_state(&mut self);
-    fn previous_card(&mut self) -> Option<Weak<RefCell<T>>>;
-    fn shuffle(&mut self);
+    fn previous_card(&mut self) -> Option<W>;
+    fn shuffle(&mut self) {
+        self.shuffle_with_rng(&mut thread_rng());
+    }
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore);
     fn add_previous_cards_to_deck(&mut self);
     fn num_of_cards_in_deck(&self) -> usize;
     fn num_of_cards_seen(&self) -> usize;
+    fn grade_current_card(&mut self, grade: Grade);
+    fn current_card_grade(&self) -> Option<Grade>;
+    // Reversed cards are shown back first and answered with their front.
+    fn is_current_card_reversed(&self) -> bool;
+    fn stats(&self) -> &SessionStats;
+    // Stops the clock on the card being studied, so the report includes it.
+    fn finish_session(&mut self);
+}
+
+impl<T, W, M> FlashCardsManager<T, W> for Box<M>
+where
+    T: FlipFlashCard,
+    M: FlashCardsManager<T, W> + ?Sized,
+{
+    fn next_card(&mut self) -> Option<W> {
+        (**self).next_card()
+    }
+    fn current_card(&mut self) -> Option<W> {
+        (**self).current_card()
+    }
+    fn flip_current_card(&mut self) {
+        (**self).flip_current_card()
+    }
+    fn try_to_flip_current_card_to_hint(&mut self) {
+        (**self).try_to_flip_current_card_to_hint()
+    }
+    fn reset_current_card_state(&mut self) {
+        (**self).reset_current_card_state()
+    }
+    fn previous_card(&mut self) -> Option<W> {
+        (**self).previous_card()
+    }
+    fn shuffle_with_rng(&mut self, rng: &mut dyn RngCore) {
+        (**self).shuffle_with_rng(rng)
+    }
+    fn add_previous_cards_to_deck(&mut self) {
+        (**self).add_previous_cards_to_deck()
+    }
+    fn num_of_cards_in_deck(&self) -> usize {
+        (**self).num_of_cards_in_deck()
+    }
+    fn num_of_cards_seen(&self) -> usize {
+        (**self).num_of_cards_seen()
+    }
+    fn grade_current_card(&mut self, grade: Grade) {
+        (**self).grade_current_card(grade)
+    }
+    fn current_card_grade(&self) -> Option<Grade> {
+        (**self).current_card_grade()
+    }
+    fn is_current_card_reversed(&self) -> bool {
+        (**self).is_current_card_reversed()
+    }
+    fn stats(&self) -> &SessionStats {
+        (**self).stats()
+    }
+    fn finish_session(&mut self) {
+        (**self).finish_session()
+    }
+}
+
+// The reference a manager hands out for a card. Reading gives None once the
+// card has left the manager, or when it can't be read without panicking.
+pub trait CardRef<T> {
+    fn read<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R>;
+}
+
+// The cell a manager keeps each card in: Rc<RefCell<T>> for CardsManager
+// and Arc<Mutex<T>> for SyncCardsManager. A RefCell gives None when the card
+// is already borrowed, as borrowing it again would panic. A Mutex waits for
+// the other thread to let go of the card.
+pub trait CardCell: Clone {
+    type Card: FlipFlashCard;
+    type Weak;
+    type Ref<'a>: Deref<Target = Self::Card>
+    where
+        Self: 'a;
+    type RefMut<'a>: DerefMut<Target = Self::Card>
+    where
+        Self: 'a;
+
+    fn new(card: Self::Card) -> Self;
+    fn downgrade(&self) -> Self::Weak;
+    fn get_card(&self) -> Option<Self::Ref<'_>>;
+    fn get_card_mut(&self) -> Option<Self::RefMut<'_>>;
+    // Identifies the card, both directions of a card share one cell.
+    fn as_ptr(&self) -> *const ();
+}
+
+pub trait Scheduler {
+    type State: Default + Debug + Clone;
+
+    fn review(&self, state: &Self::State, grade: Grade, now: SystemTime) -> Self::State;
+    fn due(&self, state: &Self::State) -> Option<SystemTime>;
 }
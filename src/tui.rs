@@ -0,0 +1,349 @@
+use std::marker::PhantomData;
+
+use rand::RngCore;
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Gauge, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+
+use crate::traits::{CardRef, FlashCardsManager};
+use crate::{FlipFlashCard, Grade, SessionReport};
+
+const HELP: &str =
+    "←/p previous  →/n next  space/f flip  h hint  1-4 grade  s shuffle  r restart  q quit";
+
+struct App<'a, T, W, M>
+where
+    M: FlashCardsManager<T, W> + ?Sized,
+    T: FlipFlashCard,
+    W: CardRef<T>,
+{
+    manager: &'a mut M,
+    // Used for shuffling, so a seeded session stays reproducible.
+    rng: &'a mut dyn RngCore,
+    card: Option<W>,
+    total_cards: usize,
+    // Taken from the manager's stats once the session is over.
+    report: Option<SessionReport>,
+    card_type: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, W, M> App<'a, T, W, M>
+where
+    M: FlashCardsManager<T, W> + ?Sized,
+    T: FlipFlashCard,
+    W: CardRef<T>,
+{
+    fn new(manager: &'a mut M, rng: &'a mut dyn RngCore) -> Self {
+        let card = manager.next_card();
+        let total_cards = manager.num_of_cards_seen() + manager.num_of_cards_in_deck();
+        App {
+            manager,
+            rng,
+            card,
+            total_cards,
+            report: None,
+            card_type: PhantomData,
+        }
+    }
+
+    fn finish(&mut self) {
+        self.manager.finish_session();
+        self.report = Some(self.manager.stats().report(3));
+    }
+
+    fn next(&mut self) {
+        match self.manager.next_card() {
+            Some(card) => self.card = Some(card),
+            // Moving past the last card ends the session.
+            None => self.finish(),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('n') => self.next(),
+            KeyCode::Left | KeyCode::Char('p') => {
+                self.manager.previous_card();
+                self.card = self.manager.current_card();
+            }
+            KeyCode::Char(' ') | KeyCode::Char('f') => self.manager.flip_current_card(),
+            KeyCode::Char('h') => self.manager.try_to_flip_current_card_to_hint(),
+            KeyCode::Char(key @ '1'..='4') if self.card.is_some() => {
+                let grade = match key {
+                    '1' => Grade::Again,
+                    '2' => Grade::Hard,
+                    '3' => Grade::Good,
+                    _ => Grade::Easy,
+                };
+                self.manager.grade_current_card(grade);
+                self.next();
+            }
+            KeyCode::Char('s') => self.manager.shuffle_with_rng(self.rng),
+            KeyCode::Char('r') => {
+                self.manager.add_previous_cards_to_deck();
+                self.card = self.manager.next_card();
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.finish(),
+            _ => {}
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        if let Some(report) = &self.report {
+            self.draw_summary(frame, report);
+            return;
+        }
+
+        let [card_area, progress_area, help_area] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let card = self.card.as_ref().and_then(|card| {
+            card.read(|card| (format!(" {} ", card.get_state()), card.to_string()))
+        });
+        let (title, text) = card.unwrap_or_else(|| (String::new(), "No cards".to_string()));
+        let block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(card_area);
+        frame.render_widget(block, card_area);
+        render_centered(frame, inner, &text);
+
+        let seen = self.manager.num_of_cards_seen();
+        let total = self.total_cards;
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            seen as f64 / total as f64
+        };
+        let progress = Gauge::default()
+            .block(Block::bordered().title(" Progress "))
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(ratio.min(1.0))
+            .label(format!("{}/{}", seen, total));
+        frame.render_widget(progress, progress_area);
+
+        frame.render_widget(Paragraph::new(HELP).alignment(Alignment::Center), help_area);
+    }
+
+    fn draw_summary(&self, frame: &mut Frame, report: &SessionReport) {
+        let text = format!(
+            "Session complete\n\n\
+             Cards seen: {}/{}\n\
+             Flips: {}\n\
+             Hints: {}\n\
+             Graded: {}\n\n\
+             Press any key to exit",
+            report.cards_studied, self.total_cards, report.flips, report.hints, report.reviews
+        );
+
+        let block = Block::bordered().title(" Summary ");
+        let inner = block.inner(frame.area());
+        frame.render_widget(block, frame.area());
+        render_centered(frame, inner, &text);
+    }
+}
+
+fn render_centered(frame: &mut Frame, area: Rect, text: &str) {
+    let height = text.lines().count().max(1) as u16;
+    let [_, middle, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+
+    let paragraph = Paragraph::new(text.to_string())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, middle);
+}
+
+fn run_app<T, W, M, B>(
+    terminal: &mut Terminal<B>,
+    manager: &mut M,
+    rng: &mut dyn RngCore,
+) -> std::io::Result<SessionReport>
+where
+    M: FlashCardsManager<T, W> + ?Sized,
+    T: FlipFlashCard,
+    W: CardRef<T>,
+    B: Backend,
+{
+    let mut app = App::new(manager, rng);
+
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(report) = app.report.take() {
+                return Ok(report);
+            }
+            app.handle_key(key.code);
+        }
+    }
+}
+
+// Drives any FlashCardsManager, such as a CardsManager, a filtered or a
+// thread-safe one, or a Box<dyn FlashCardsManager<T>>.
+pub fn run<T, W, M>(manager: &mut M, rng: &mut dyn RngCore) -> std::io::Result<SessionReport>
+where
+    M: FlashCardsManager<T, W> + ?Sized,
+    T: FlipFlashCard,
+    W: CardRef<T>,
+{
+    let mut terminal = ratatui::try_init()?;
+    let result = run_app(&mut terminal, manager, rng);
+    ratatui::try_restore()?;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::tests::{create_sized_test_manager, create_test_cards};
+    use crate::{Card, Cards, CardsManager, FilteredCardsManager, FlashCard, SyncCardsManager};
+    use ratatui::backend::TestBackend;
+
+    fn render<T, W, M>(app: &App<T, W, M>) -> String
+    where
+        M: FlashCardsManager<T, W> + ?Sized,
+        T: FlipFlashCard,
+        W: CardRef<T>,
+    {
+        let mut terminal = Terminal::new(TestBackend::new(60, 14)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        buffer
+            .content()
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_render_card() {
+        let mut manager = create_sized_test_manager(2);
+        let mut rng = crate::seeded_rng(1);
+        let mut app = App::new(&mut manager, &mut rng);
+
+        let screen = render(&app);
+        assert!(screen.contains(" front "));
+        assert!(screen.contains("0 - front"));
+        assert!(screen.contains("1/2"));
+
+        app.handle_key(KeyCode::Char(' '));
+        let screen = render(&app);
+        assert!(screen.contains(" back "));
+        assert!(screen.contains("0 - back"));
+    }
+
+    #[test]
+    fn test_shuffle_uses_rng() {
+        let mut expected = create_sized_test_manager(10);
+        expected.next_card();
+        expected.shuffle_with_rng(&mut crate::seeded_rng(7));
+        let expected = expected.next_card().unwrap().upgrade().unwrap();
+
+        let mut manager = create_sized_test_manager(10);
+        let mut rng = crate::seeded_rng(7);
+        let mut app = App::new(&mut manager, &mut rng);
+        app.handle_key(KeyCode::Char('s'));
+        app.handle_key(KeyCode::Right);
+
+        let card = app.card.unwrap().upgrade().unwrap();
+        assert_eq!(expected.borrow().get_front(), card.borrow().get_front());
+    }
+
+    #[test]
+    fn test_drives_any_manager() {
+        let mut rng = crate::seeded_rng(1);
+        let mut filtered = FilteredCardsManager::create_from_deck(create_test_cards(4), |card| {
+            card.get_front().starts_with('3')
+        });
+        let mut app = App::new(&mut filtered, &mut rng);
+        assert!(render(&app).contains("3 - front"));
+        app.handle_key(KeyCode::Right);
+        assert_eq!(Some(1), app.report.map(|report| report.cards_studied));
+
+        let mut manager = SyncCardsManager::create_from_deck(create_test_cards(2));
+        let mut app = App::new(&mut manager, &mut rng);
+        app.handle_key(KeyCode::Char('f'));
+        assert!(render(&app).contains("0 - back"));
+
+        let mut manager: Box<dyn FlashCardsManager<Card>> = Box::new(create_sized_test_manager(2));
+        let mut app = App::new(&mut manager, &mut rng);
+        app.handle_key(KeyCode::Char('q'));
+        assert_eq!(Some(1), app.report.map(|report| report.cards_studied));
+    }
+
+    #[test]
+    fn test_session_summary() {
+        let mut manager = create_sized_test_manager(2);
+        let mut rng = crate::seeded_rng(1);
+        let mut app = App::new(&mut manager, &mut rng);
+
+        app.handle_key(KeyCode::Char('f'));
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Char('3'));
+        assert!(app.report.is_none());
+        app.handle_key(KeyCode::Right);
+        assert!(app.report.is_some());
+
+        let report = app.report.clone().unwrap();
+        assert_eq!(
+            (
+                report.cards_studied,
+                report.flips,
+                report.hints,
+                report.reviews
+            ),
+            (2, 1, 1, 1)
+        );
+        let screen = render(&app);
+        assert!(screen.contains("Session complete"));
+        assert!(screen.contains("Cards seen: 2/2"));
+    }
+
+    #[test]
+    fn test_session_summary_counts_only_real_flips_and_hints() {
+        let mut card = Card::new("France".to_string(), "Paris".to_string(), String::new());
+        card.set_hint(None);
+        let cards: Cards<Card> = vec![card].into_iter().collect();
+        let mut manager = CardsManager::create_from_deck(cards);
+        let mut rng = crate::seeded_rng(1);
+        let mut app = App::new(&mut manager, &mut rng);
+
+        // The card has no hint.
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Char('q'));
+        assert_eq!(0, app.report.unwrap().hints);
+
+        let mut manager = create_sized_test_manager(2);
+        let mut rng = crate::seeded_rng(1);
+        let mut app = App::new(&mut manager, &mut rng);
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Left);
+        assert!(app.card.is_none());
+        app.handle_key(KeyCode::Char('f'));
+        app.handle_key(KeyCode::Char('h'));
+        app.handle_key(KeyCode::Char('q'));
+
+        let report = app.report.unwrap();
+        assert_eq!((0, 1), (report.flips, report.hints));
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{FlashCard, FlashCards};
+
+const DEFAULT_MAX_LENGTH: usize = 500;
+
+#[derive(Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Problem {
+    Empty { field: String },
+    HintSameAsBack,
+    DuplicateFront { first_card: usize },
+    DuplicateCard { first_card: usize },
+    Whitespace { field: String },
+    TooLong { field: String, length: usize },
+}
+
+impl Problem {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Problem::Empty { .. } | Problem::DuplicateCard { .. } => Severity::Error,
+            Problem::HintSameAsBack | Problem::DuplicateFront { .. } | Problem::TooLong { .. } => {
+                Severity::Warning
+            }
+            Problem::Whitespace { .. } => Severity::Info,
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Empty { field } => write!(f, "{} is empty", field),
+            Problem::HintSameAsBack => write!(f, "hint is the same as the back"),
+            Problem::DuplicateFront { first_card } => {
+                write!(f, "front is the same as card {}", first_card)
+            }
+            Problem::DuplicateCard { first_card } => {
+                write!(f, "front and back are the same as card {}", first_card)
+            }
+            Problem::Whitespace { field } => {
+                write!(f, "{} has leading or trailing whitespace", field)
+            }
+            Problem::TooLong { field, length } => {
+                write!(f, "{} is {} characters long", field, length)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    // Cards are numbered from 1 in the order the deck holds them.
+    pub card: usize,
+    pub card_id: String,
+    pub problem: Problem,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: card {}: {}", self.severity, self.card, self.problem)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    max_length: usize,
+}
+
+impl Validator {
+    pub fn new(max_length: usize) -> Self {
+        Validator { max_length }
+    }
+
+    pub fn validate<T>(&self, deck: &dyn FlashCards<T>) -> Vec<Finding>
+    where
+        T: for<'de> FlashCard<'de>,
+    {
+        let mut findings = Vec::new();
+        let mut fronts: HashMap<String, usize> = HashMap::new();
+        let mut pairs: HashMap<(String, String), usize> = HashMap::new();
+
+        for (index, card) in deck.iter().enumerate() {
+            let number = index + 1;
+            let front = card.get_front();
+            let back = card.get_back();
+            let hint = card.get_hint();
+
+            let mut problems = Vec::new();
+
+            let mut fields = vec![("front", &front), ("back", &back)];
+            if let Some(hint) = &hint {
+                fields.push(("hint", hint));
+            }
+            for (field, text) in fields {
+                let length = text.chars().count();
+                if text.trim().is_empty() {
+                    if field != "hint" {
+                        problems.push(Problem::Empty {
+                            field: field.to_string(),
+                        });
+                    }
+                } else if text.trim() != text.as_str() {
+                    problems.push(Problem::Whitespace {
+                        field: field.to_string(),
+                    });
+                }
+                if length > self.max_length {
+                    problems.push(Problem::TooLong {
+                        field: field.to_string(),
+                        length,
+                    });
+                }
+            }
+
+            if !back.trim().is_empty()
+                && hint.as_ref().is_some_and(|hint| hint.trim() == back.trim())
+            {
+                problems.push(Problem::HintSameAsBack);
+            }
+
+            let front_key = front.trim().to_string();
+            let pair_key = (front_key.clone(), back.trim().to_string());
+            if let Some(first_card) = pairs.get(&pair_key) {
+                problems.push(Problem::DuplicateCard {
+                    first_card: *first_card,
+                });
+            } else if let Some(first_card) = fronts.get(&front_key) {
+                problems.push(Problem::DuplicateFront {
+                    first_card: *first_card,
+                });
+            }
+            fronts.entry(front_key).or_insert(number);
+            pairs.entry(pair_key).or_insert(number);
+
+            findings.extend(problems.into_iter().map(|problem| Finding {
+                severity: problem.severity(),
+                card: number,
+                card_id: card.get_id(),
+                problem,
+            }));
+        }
+
+        // Most severe first, and in deck order within a severity.
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        findings
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Validator::new(DEFAULT_MAX_LENGTH)
+    }
+}
+
+pub fn has_errors(findings: &[Finding]) -> bool {
+    findings
+        .iter()
+        .any(|finding| finding.severity == Severity::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Card, Cards};
+
+    fn create_test_deck(rows: &[(&str, &str, Option<&str>)]) -> Cards<Card> {
+        rows.iter()
+            .map(|(front, back, hint)| {
+                let mut card = Card::new(front.to_string(), back.to_string(), String::new());
+                card.set_hint(hint.map(|hint| hint.to_string()));
+                card
+            })
+            .collect()
+    }
+
+    fn problems(findings: &[Finding]) -> Vec<(usize, Problem)> {
+        findings
+            .iter()
+            .map(|finding| (finding.card, finding.problem.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_deck() {
+        let deck = create_test_deck(&[
+            ("France", "Paris", Some("Eiffel")),
+            ("Spain", "Madrid", None),
+        ]);
+
+        let findings = Validator::default().validate(&deck);
+
+        assert!(findings.is_empty());
+        assert!(!has_errors(&findings));
+    }
+
+    #[test]
+    fn test_empty_and_whitespace() {
+        let deck = create_test_deck(&[(" ", "Paris", None), ("Spain ", "", Some(""))]);
+
+        let findings = Validator::default().validate(&deck);
+
+        assert_eq!(
+            problems(&findings),
+            vec![
+                (
+                    1,
+                    Problem::Empty {
+                        field: "front".to_string()
+                    }
+                ),
+                (
+                    2,
+                    Problem::Empty {
+                        field: "back".to_string()
+                    }
+                ),
+                (
+                    2,
+                    Problem::Whitespace {
+                        field: "front".to_string()
+                    }
+                ),
+            ]
+        );
+        assert!(has_errors(&findings));
+    }
+
+    #[test]
+    fn test_hint_same_as_back() {
+        let deck = create_test_deck(&[("France", "Paris", Some("Paris"))]);
+
+        let findings = Validator::default().validate(&deck);
+
+        assert_eq!(problems(&findings), vec![(1, Problem::HintSameAsBack)]);
+        assert_eq!(findings[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let deck = create_test_deck(&[
+            ("France", "Paris", None),
+            ("France", "Lyon", None),
+            ("France", "Paris", None),
+        ]);
+
+        let findings = Validator::default().validate(&deck);
+
+        assert_eq!(
+            problems(&findings),
+            vec![
+                (3, Problem::DuplicateCard { first_card: 1 }),
+                (2, Problem::DuplicateFront { first_card: 1 }),
+            ]
+        );
+        assert_eq!(findings[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_too_long() {
+        let deck = create_test_deck(&[("France", "Paris is the capital", None)]);
+
+        let findings = Validator::new(10).validate(&deck);
+
+        assert_eq!(
+            problems(&findings),
+            vec![(
+                1,
+                Problem::TooLong {
+                    field: "back".to_string(),
+                    length: 20
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_finding_display_and_json() {
+        let deck = create_test_deck(&[("France", "", None)]);
+
+        let findings = Validator::default().validate(&deck);
+
+        assert_eq!(findings[0].to_string(), "error: card 1: back is empty");
+        let json = serde_json::to_string(&findings[0]).unwrap();
+        assert!(json.contains(r#""severity":"error""#));
+        assert!(json.contains(r#""problem":{"kind":"empty","field":"back"}"#));
+    }
+}